@@ -0,0 +1,120 @@
+use assay::assay;
+use cruct::{ConfigWarning, cruct};
+
+#[cruct]
+#[derive(Debug)]
+struct Database {
+    #[field(alias = "uri", deprecated = "use `url` instead")]
+    url: String,
+}
+
+#[assay(include = ["tests/fixtures/e2e/aliases/old.toml"])]
+fn deprecated_aliases_load_with_warnings() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/aliases/old.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        #[field(alias = "listen_port", alias = "port", deprecated = "use `http_port` instead")]
+        http_port: u16,
+
+        #[field(alias = "log")]
+        log_level: String,
+
+        #[field(default = 1, deprecated = "workers are sized automatically")]
+        workers: u32,
+
+        database: Database,
+    }
+
+    let (cfg, warnings) = Config::loader()
+        .with_config()
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 8080);
+    assert_eq!(cfg.log_level, "debug");
+    assert_eq!(cfg.workers, 4);
+    assert_eq!(
+        cfg.database
+            .url,
+        "postgres://old"
+    );
+
+    assert_eq!(
+        warnings,
+        [
+            ConfigWarning::DeprecatedKey {
+                key: "port".into(),
+                message: "use `http_port` instead".into(),
+            },
+            ConfigWarning::DeprecatedKey {
+                key: "workers".into(),
+                message: "workers are sized automatically".into(),
+            },
+            ConfigWarning::DeprecatedKey {
+                key: "database.uri".into(),
+                message: "use `url` instead".into(),
+            },
+        ]
+    );
+    assert_eq!(warnings[0].to_string(), "Key 'port' is deprecated: use `http_port` instead");
+}
+
+#[assay(include = ["tests/fixtures/e2e/aliases/new.toml"])]
+fn keys_take_precedence_over_aliases() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/aliases/new.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        #[field(alias = "port", deprecated = "use `http_port` instead")]
+        http_port: u16,
+
+        #[field(alias = "log")]
+        log_level: String,
+
+        database: Database,
+    }
+
+    let (cfg, warnings) = Config::loader()
+        .with_config()
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 9090);
+    assert_eq!(cfg.log_level, "info");
+    assert_eq!(
+        cfg.database
+            .url,
+        "postgres://new"
+    );
+    assert_eq!(
+        warnings,
+        [ConfigWarning::ConflictingKey {
+            key: "port".into(),
+            used: "http_port".into()
+        }]
+    );
+    assert_eq!(warnings[0].to_string(), "Key 'port' is ignored, as 'http_port' is also set");
+}
+
+#[assay(include = ["tests/fixtures/e2e/aliases/mixed_case.toml"])]
+fn insensitive_aliases_conflict() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/aliases/mixed_case.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        #[field(alias = "log", insensitive = true)]
+        log_level: String,
+    }
+
+    let (cfg, warnings) = Config::loader()
+        .with_config()
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(cfg.log_level, "info");
+    assert_eq!(
+        warnings,
+        [ConfigWarning::ConflictingKey {
+            key: "log".into(),
+            used: "log_level".into()
+        }]
+    );
+}
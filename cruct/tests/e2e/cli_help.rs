@@ -0,0 +1,142 @@
+use cruct::{ConfigWarning, ParserError, cruct};
+
+#[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+#[derive(Debug)]
+struct App {
+    /// The port to listen on.
+    #[field(default = 8080, arg_override = "port", env_override = "APP_PORT")]
+    http_port: u16,
+
+    /// Enables verbose logging.
+    #[field(default = false, arg_override = "verbose")]
+    verbose: bool,
+
+    nested: Nested,
+}
+
+#[cruct]
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Nested {
+    /// Items to process,
+    /// one per flag.
+    #[field(arg_override = "item")]
+    items: Vec<String>,
+
+    #[field(name = "display_name", default = "app".to_string())]
+    name: String,
+}
+
+#[test]
+fn cli_definition_lists_overridable_fields() {
+    let flags = App::cli_definition().flags;
+
+    let names = flags
+        .iter()
+        .map(|flag| flag.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["port", "verbose", "item"]);
+
+    let keys = flags
+        .iter()
+        .map(|flag| {
+            flag.key
+                .as_str()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["http_port", "verbose", "nested.items"]);
+}
+
+#[test]
+fn help_lists_types_defaults_and_descriptions() {
+    let err = App::loader()
+        .with_cli(0)
+        .with_strict_cli()
+        .with_args(["--verbose", "--help"])
+        .load()
+        .unwrap_err();
+
+    let ParserError::HelpRequested(help) = err else {
+        panic!("expected help to be requested, found {err}");
+    };
+
+    assert!(help.starts_with("Usage: cruct [OPTIONS]"));
+    assert!(help.contains("--port <u16>"));
+    assert!(help.contains("The port to listen on. [default: 8080] [env: APP_PORT]"));
+    assert!(help.contains("--verbose "));
+    assert!(help.contains("--item <Vec<String>>"));
+    assert!(help.contains("Items to process, one per flag."));
+    assert!(!help.contains("display_name"));
+}
+
+#[test]
+fn unknown_flags_are_warned_about() {
+    let (app, warnings) = App::loader()
+        .with_config()
+        .with_cli(0)
+        .with_args(["--help", "--verbos", "--nested.display_name=x"])
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(
+        app.nested
+            .name,
+        "x"
+    );
+    let flags = warnings
+        .into_iter()
+        .filter(|warning| matches!(warning, ConfigWarning::UnknownFlag { .. }))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        flags,
+        [
+            ConfigWarning::UnknownFlag { flag: "help".into(), suggestion: None },
+            ConfigWarning::UnknownFlag {
+                flag: "verbos".into(),
+                suggestion: Some("verbose".into())
+            },
+        ]
+    );
+}
+
+#[test]
+fn unknown_and_malformed_flags_are_reported() {
+    let err = App::loader()
+        .with_config()
+        .with_cli(0)
+        .with_strict_cli()
+        .with_args(["--http_port=1"])
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(err, ParserError::UnknownFlag(flag) if flag == "http_port"));
+
+    let err = App::loader()
+        .with_config()
+        .with_cli(0)
+        .with_strict_cli()
+        .with_args(["--port"])
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(err, ParserError::MalformedFlag { flag, .. } if flag == "--port"));
+}
+
+#[test]
+fn defined_flags_override_config() {
+    let app = App::loader()
+        .with_config()
+        .with_cli(0)
+        .with_args(["--port", "9000", "--nested.items", "x", "--verbose"])
+        .load()
+        .unwrap();
+
+    assert_eq!(app.http_port, 9000);
+    assert!(app.verbose);
+    assert_eq!(
+        app.nested
+            .items,
+        ["x"]
+    );
+}
@@ -1,22 +1,81 @@
-use assay::assay;
 use cruct::cruct;
 
-#[assay(
-    include = ["tests/fixtures/integration/cli.toml"],
-)]
+#[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+#[derive(Debug)]
+struct C {
+    #[field(default = 1, arg_override = "number")]
+    n: u32,
+
+    #[field(default = 0)]
+    http_port: u16,
+
+    nested: Nested,
+}
+
+#[cruct]
+#[derive(Debug)]
+struct Nested {
+    items: Vec<String>,
+
+    #[field(default = false)]
+    enabled: bool,
+}
+
+#[test]
 fn cli_override_takes_highest_priority() {
-    #[cruct(load_config(path = "tests/fixtures/basic.toml"))]
-    #[derive(Debug)]
-    struct C {
-        #[field(default = 1, arg_override = "number")]
-        n: u32,
-    }
+    let c = C::loader()
+        .with_config()
+        .with_args(["--number=999"])
+        .load()
+        .unwrap();
 
+    assert_eq!(c.n, 999);
+}
+
+#[test]
+fn cli_source_overrides_config_files() {
     let c = C::loader()
         .with_config()
         .with_cli(0)
+        .with_args([
+            "--http_port",
+            "9090",
+            "--nested.items=x",
+            "--nested.items=y",
+            "--nested.enabled",
+        ])
         .load()
         .unwrap();
 
-    assert_eq!(c.n, 999);
+    assert_eq!(c.n, 1);
+    assert_eq!(c.http_port, 9090);
+    assert_eq!(
+        c.nested
+            .items,
+        ["x", "y"]
+    );
+    assert!(
+        c.nested
+            .enabled
+    );
+}
+
+#[test]
+fn positional_arguments_are_ignored() {
+    let c = C::loader()
+        .with_config()
+        .with_cli(0)
+        .with_args(["input.txt", "--nested.enabled", "output.txt", "--unknown"])
+        .load()
+        .unwrap();
+
+    assert!(
+        c.nested
+            .enabled
+    );
+    assert_eq!(
+        c.nested
+            .items,
+        ["foo", "bar"]
+    );
 }
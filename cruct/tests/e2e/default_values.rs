@@ -152,3 +152,157 @@ fn test_char_default() {
 
     assert_eq!(config.value, 'a');
 }
+
+#[test]
+fn test_trait_default() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/value.toml"))]
+    #[derive(Debug, PartialEq)]
+    struct TestTraitDefault {
+        #[field(default)]
+        name: String,
+        #[field(default)]
+        tags: Vec<String>,
+    }
+
+    let config = TestTraitDefault::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.name, "");
+    assert!(
+        config
+            .tags
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_trait_default_is_qualified() {
+    #[allow(dead_code)]
+    trait Default {}
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/value.toml"))]
+    #[derive(Debug, PartialEq)]
+    struct TestQualifiedDefault {
+        #[field(default)]
+        port: u16,
+    }
+
+    let config = TestQualifiedDefault::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.port, 0);
+    assert_eq!(
+        TestQualifiedDefault::field_metadata()[0].default,
+        Some(cruct::DefaultValue::Expr("Default::default()"))
+    );
+}
+
+#[test]
+fn test_default_fn() {
+    fn default_port() -> u16 {
+        8080
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/value.toml"))]
+    #[derive(Debug, PartialEq)]
+    struct TestDefaultFn {
+        #[field(default_fn = default_port)]
+        port: u16,
+    }
+
+    let config = TestDefaultFn::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn test_struct_default() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/partial.toml"), default)]
+    #[derive(Debug, PartialEq)]
+    struct TestStructDefault {
+        host: String,
+        port: u16,
+    }
+
+    impl Default for TestStructDefault {
+        fn default() -> Self {
+            Self {
+                host: "localhost".to_string(),
+                port: 3000,
+            }
+        }
+    }
+
+    let config = TestStructDefault::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.host, "example.com");
+    assert_eq!(config.port, 3000);
+}
+
+#[test]
+fn test_omitted_section_default() {
+    #[cruct(default)]
+    #[derive(Debug, PartialEq)]
+    struct Database {
+        url: String,
+        pool_size: u32,
+    }
+
+    impl Default for Database {
+        fn default() -> Self {
+            Self {
+                url: "postgres://localhost".to_string(),
+                pool_size: 10,
+            }
+        }
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/partial.toml"))]
+    #[derive(Debug, PartialEq)]
+    struct TestOmittedSection {
+        host: String,
+        database: Database,
+    }
+
+    let config = TestOmittedSection::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.host, "example.com");
+    assert_eq!(config.database, Database::default());
+}
+
+#[test]
+fn test_omitted_section_parent_default() {
+    #[cruct]
+    #[derive(Debug, Default, PartialEq)]
+    struct Server {
+        port: u16,
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/defaults/partial.toml"), default)]
+    #[derive(Debug, Default, PartialEq)]
+    struct TestParentDefault {
+        host: String,
+        server: Server,
+    }
+
+    let config = TestParentDefault::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(config.host, "example.com");
+    assert_eq!(config.server, Server { port: 0 });
+}
@@ -0,0 +1,244 @@
+use assay::assay;
+use cruct::{ParserError, cruct};
+
+#[cruct(rename_all = "kebab-case")]
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Primary,
+    ReadReplica,
+    #[variant(name = "off")]
+    Disabled,
+}
+
+#[cruct(insensitive = true, rename_all = "lowercase")]
+#[derive(Debug, PartialEq)]
+enum Level {
+    Info,
+    Warn,
+}
+
+#[cruct(rename_all = "lowercase")]
+#[derive(Debug, PartialEq)]
+enum Backend {
+    S3 {
+        bucket: String,
+
+        #[field(default = "us-east-1".to_string())]
+        region: String,
+    },
+    Local(String),
+}
+
+#[cruct(rename_all = "lowercase")]
+#[derive(Debug, PartialEq)]
+enum Cache {
+    Redis(String),
+    Memory,
+}
+
+#[cruct(rename_all = "lowercase")]
+#[derive(Debug, PartialEq)]
+enum Limits {
+    Window(u32, u32),
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct LocalBackend {
+    path: String,
+}
+
+#[cruct(tag = "type", rename_all = "lowercase")]
+#[derive(Debug, PartialEq)]
+enum Storage {
+    S3 { bucket: String, region: String },
+    Local(LocalBackend),
+    Memory,
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/external.toml"])]
+fn externally_tagged_enums() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/external.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        mode: Mode,
+        level: Level,
+        replicas: Vec<Mode>,
+        backend: Backend,
+        cache: Cache,
+        limits: Limits,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.mode, Mode::Primary);
+    assert_eq!(cfg.level, Level::Warn);
+    assert_eq!(cfg.replicas, [Mode::Primary, Mode::ReadReplica]);
+    assert_eq!(
+        cfg.backend,
+        Backend::S3 {
+            bucket: "assets".into(),
+            region: "us-east-1".into()
+        }
+    );
+    assert_eq!(cfg.cache, Cache::Redis("redis://localhost".into()));
+    assert_eq!(cfg.limits, Limits::Window(10, 60));
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/internal.yml"])]
+fn internally_tagged_enums() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/internal.yml"))]
+    #[derive(Debug)]
+    struct Config {
+        storage: Storage,
+        fallback: Storage,
+        scratch: Storage,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        cfg.storage,
+        Storage::S3 {
+            bucket: "assets".into(),
+            region: "eu-west-1".into()
+        }
+    );
+    assert_eq!(cfg.fallback, Storage::Local(LocalBackend { path: "/var/lib/app".into() }));
+    assert_eq!(cfg.scratch, Storage::Memory);
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/unknown.toml"])]
+fn unknown_variants_are_reported() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/unknown.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        mode: Mode,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    let ParserError::NestedError { section, source } = err else {
+        panic!("expected a nested error, found {err}");
+    };
+
+    assert_eq!(section, "mode");
+    assert_eq!(
+        source.to_string(),
+        "Unknown variant 'standby' for 'Mode', expected one of: primary, read-replica, off"
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/missing.toml"])]
+fn variant_errors_name_the_variant() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/missing.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        storage: Storage,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Nested configuration error in storage: Nested configuration error in s3: Missing \
+         required field: bucket"
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/unknown.toml"])]
+fn optional_enums_keep_unknown_variants() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/unknown.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        #[field(optional = true)]
+        mode: Option<Mode>,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    let ParserError::NestedError { section, source } = err else {
+        panic!("expected a nested error, found {err}");
+    };
+
+    assert_eq!(section, "mode");
+    assert!(matches!(
+        *source,
+        ParserError::UnknownVariant { ref found, ref expected, .. }
+            if found == "standby" && expected == &["primary", "read-replica", "off"]
+    ));
+}
+
+#[assay(include = ["tests/fixtures/e2e/enums/invalid.toml"])]
+fn internal_variant_paths_name_the_variant() {
+    #[cruct(tag = "type", rename_all = "lowercase")]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Checked {
+        S3 {
+            bucket: String,
+
+            #[field(length(min = 1))]
+            region: String,
+        },
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/enums/invalid.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        storage: Checked,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for 'storage.s3.region': length must be at least 1, found 0"
+    );
+}
+
+#[test]
+fn unit_enums_list_their_variants() {
+    #[cruct]
+    #[allow(dead_code)]
+    struct Config {
+        /// The replication mode.
+        mode: Mode,
+    }
+
+    let schema = Config::json_schema();
+
+    assert!(schema.contains(
+        r#""enum": [
+        "primary",
+        "read-replica",
+        "off"
+      ]"#
+    ));
+    assert!(
+        Config::markdown_reference()
+            .contains("The replication mode. One of `primary`, `read-replica`, `off`.")
+    );
+}
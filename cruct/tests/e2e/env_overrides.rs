@@ -1,5 +1,5 @@
 use assay::assay;
-use cruct::cruct;
+use cruct::{ConfigValue, FromConfigValue, cruct};
 
 #[assay(
     include = ["tests/fixtures/test_config.toml"],
@@ -22,3 +22,111 @@ fn test_env_override() {
 
     assert_eq!(config.http_port, 9999);
 }
+
+#[assay(
+    env = [
+        ("TEST_HTTP_PORT", "9999")
+    ],
+)]
+fn from_config_value_reads_process_environment() {
+    #[cruct]
+    #[derive(Debug, PartialEq)]
+    struct TestEnv {
+        #[field(env_override = "TEST_HTTP_PORT")]
+        http_port: u16,
+    }
+
+    let config = TestEnv::from_config_value(&ConfigValue::Section(Default::default())).unwrap();
+
+    assert_eq!(config.http_port, 9999);
+}
+
+#[test]
+fn env_provider_replaces_process_environment() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/interpolation.toml"),
+        interpolate = true,
+        profile_env = "TEST_PROFILE"
+    )]
+    #[derive(Debug)]
+    struct TestProvider {
+        database_url: String,
+
+        #[field(env_override = "TEST_HTTP_PORT")]
+        port: u16,
+    }
+
+    let loader = TestProvider::loader()
+        .with_config()
+        .with_env_provider(|name| match name {
+            "DB_HOST" => Some("db.internal".to_string()),
+            "TEST_HTTP_PORT" => Some("7777".to_string()),
+            "TEST_PROFILE" => Some("staging".to_string()),
+            _ => None,
+        });
+
+    assert_eq!(
+        loader
+            .active_profile()
+            .as_deref(),
+        Some("staging")
+    );
+
+    let config = loader
+        .load()
+        .unwrap();
+
+    assert_eq!(config.database_url, "postgres://db.internal/app");
+    assert_eq!(config.port, 7777);
+}
+
+#[test]
+fn single_value_override_fills_array() {
+    #[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+    #[derive(Debug, PartialEq)]
+    struct TestArray {
+        #[field(env_override = "TEST_ITEMS")]
+        items: Vec<String>,
+    }
+
+    let config = TestArray::loader()
+        .with_config()
+        .with_env_provider(|name| (name == "TEST_ITEMS").then(|| "x".to_string()))
+        .load()
+        .unwrap();
+
+    assert_eq!(config.items, ["x"]);
+}
+
+#[test]
+fn dotted_flags_do_not_shadow_overrides() {
+    #[cruct]
+    #[derive(Debug, PartialEq)]
+    struct TestDotted {
+        #[field(arg_override = "db", env_override = "TEST_DB", default = "sqlite".to_string())]
+        db: String,
+
+        #[field(name = "db.host", arg_override = "db.host", default = "localhost".to_string())]
+        db_host: String,
+    }
+
+    let env = |name: &str| (name == "TEST_DB").then(|| "postgres".to_string());
+
+    let config = TestDotted::loader()
+        .with_args(["--db.host=remote"])
+        .with_env_provider(env)
+        .load()
+        .unwrap();
+
+    assert_eq!(config.db, "postgres");
+    assert_eq!(config.db_host, "remote");
+
+    let config = TestDotted::loader()
+        .with_args(["--db.host=remote", "--db=mysql"])
+        .with_env_provider(env)
+        .load()
+        .unwrap();
+
+    assert_eq!(config.db, "mysql");
+    assert_eq!(config.db_host, "remote");
+}
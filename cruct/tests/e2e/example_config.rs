@@ -0,0 +1,88 @@
+use std::io::Write;
+
+use cruct::{ConfigFileSource, FileFormat, cruct};
+use tempfile::Builder;
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct App {
+    /// The port to listen on.
+    #[field(default = 8080)]
+    port: u16,
+
+    #[field(optional = true)]
+    motd: Option<String>,
+
+    #[field(default = Vec::new())]
+    tags: Vec<String>,
+
+    database: Database,
+
+    upstreams: Vec<Upstream>,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Database {
+    /// The connection string.
+    url: String,
+
+    #[field(default = 0.5)]
+    ratio: f64,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Upstream {
+    #[field(name = "address")]
+    host: String,
+
+    #[field(default = true)]
+    tls: bool,
+}
+
+/// Loads the example file rendered in the given format.
+fn load_example(format: FileFormat) -> App {
+    let mut file = Builder::new()
+        .suffix(&format!(".{format}"))
+        .tempfile()
+        .unwrap();
+
+    file.write_all(App::example_config(format).as_bytes())
+        .unwrap();
+
+    let path = file
+        .path()
+        .to_str()
+        .unwrap();
+
+    App::loader()
+        .with_source(ConfigFileSource::new(path, None))
+        .load()
+        .unwrap()
+}
+
+#[test]
+fn example_lists_keys_and_descriptions() {
+    let example = App::example_config(FileFormat::Toml);
+
+    assert!(example.starts_with("# The port to listen on.\nport = 8080\n"));
+    assert!(example.contains("# motd = \"<String>\""));
+    assert!(example.contains("# The connection string.\nurl = \"<String>\""));
+    assert!(example.contains("[[upstreams]]\naddress = \"<String>\""));
+}
+
+#[test]
+fn examples_load_in_every_format() {
+    let expected = App {
+        port: 8080,
+        motd: None,
+        tags: Vec::new(),
+        database: Database { url: "<String>".into(), ratio: 0.5 },
+        upstreams: vec![Upstream { host: "<String>".into(), tls: true }],
+    };
+
+    for format in [FileFormat::Toml, FileFormat::Yml, FileFormat::Json] {
+        assert_eq!(load_example(format), expected, "{format}");
+    }
+}
@@ -1,5 +1,5 @@
 use assay::assay;
-use cruct::cruct;
+use cruct::{ParserError, cruct};
 
 #[assay(
     include = ["tests/fixtures/e2e/flat_nested.toml"],
@@ -9,6 +9,7 @@ fn flat_nested_structs_load_correctly() {
     #[derive(Debug, PartialEq)]
     struct Outer {
         top_level: String,
+        #[field(flatten)]
         nested: Nested,
     }
 
@@ -48,6 +49,7 @@ fn flat_nested_with_defaults() {
     struct Config {
         #[field(default = "default".into())]
         name: String,
+        #[field(flatten)]
         nested: NestedWithDefaults,
     }
 
@@ -92,6 +94,7 @@ fn mixed_nested_and_flat() {
         #[field(name = "nested_section")]
         nested: NestedSection,
 
+        #[field(flatten)]
         flat: FlatNested,
     }
 
@@ -126,3 +129,133 @@ fn mixed_nested_and_flat() {
         true
     );
 }
+
+#[assay(
+    include = ["tests/fixtures/e2e/flat_nested.toml"],
+)]
+fn nested_structs_without_flatten_require_a_section() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/flat_nested.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Outer {
+        top_level: String,
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Nested {
+        value: u32,
+        flag: bool,
+    }
+
+    let result = Outer::loader()
+        .with_config()
+        .load();
+
+    assert_eq!(
+        result
+            .unwrap_err()
+            .to_string(),
+        "Missing required section: nested"
+    );
+}
+
+#[assay(
+    include = ["tests/fixtures/e2e/flat_nested.toml"],
+)]
+fn missing_sections_keep_other_errors() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/flat_nested.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Outer {
+        top_level: String,
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Nested {
+        #[field(env_override = "NESTED_VALUE")]
+        value: u32,
+    }
+
+    let result = Outer::loader()
+        .with_config()
+        .with_env_provider(|name| (name == "NESTED_VALUE").then(|| "abc".to_string()))
+        .load();
+
+    assert!(matches!(
+        result,
+        Err(ParserError::NestedError { section, source })
+            if section == "nested" && matches!(*source, ParserError::TypeMismatch { .. })
+    ));
+}
+
+#[assay(
+    include = ["tests/fixtures/e2e/flat_nested.toml"],
+)]
+fn flattened_key_collisions_are_reported() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/flat_nested.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Outer {
+        value: u32,
+        #[field(flatten)]
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Nested {
+        value: u32,
+        flag: bool,
+    }
+
+    let result = Outer::loader()
+        .with_config()
+        .load();
+
+    assert_eq!(
+        result
+            .unwrap_err()
+            .to_string(),
+        "Key 'value' of flattened field 'nested' collides with another field"
+    );
+}
+
+#[test]
+fn flattened_fields_are_described_without_a_prefix() {
+    #[cruct]
+    #[allow(dead_code)]
+    struct Outer {
+        top_level: String,
+        #[field(flatten)]
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[allow(dead_code)]
+    struct Nested {
+        value: u32,
+        flag: bool,
+    }
+
+    let keys = Outer::field_metadata()
+        .iter()
+        .map(|field| field.key)
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["top_level", "value", "flag"]);
+
+    let keys = Outer::cli_definition()
+        .keys
+        .into_iter()
+        .map(|flag| flag.key)
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["top_level", "value", "flag"]);
+}
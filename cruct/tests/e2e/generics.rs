@@ -0,0 +1,118 @@
+use assay::assay;
+use cruct::{FromConfigValue, cruct};
+
+#[cruct(load_config(path = "tests/fixtures/e2e/generics/pools.toml"))]
+#[derive(Debug, PartialEq)]
+struct Pool<T> {
+    size: u32,
+
+    settings: T,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Postgres {
+    url: String,
+
+    #[field(default = 5432)]
+    port: u16,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Wrapper<T>(T);
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+enum Either<L, R>
+where
+    L: Clone,
+{
+    Left(L),
+    Right(R),
+}
+
+#[assay(include = ["tests/fixtures/e2e/generics/pools.toml"])]
+fn generic_structs_load_their_parameters() {
+    let pool = Pool::<Postgres>::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        pool,
+        Pool {
+            size: 8,
+            settings: Postgres {
+                url: "postgres://localhost".into(),
+                port: 5432
+            },
+        }
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/generics/pools.toml"])]
+fn generic_structs_nest() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/generics/pools.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        cache: Pool<Wrapper<String>>,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        cfg.cache,
+        Pool {
+            size: 2,
+            settings: Wrapper("redis://localhost".to_string())
+        }
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/generics/pools.toml"])]
+fn generic_structs_take_struct_defaults() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/generics/pools.toml"), default)]
+    #[derive(Debug, PartialEq)]
+    struct Limits<T> {
+        size: u32,
+
+        timeout: T,
+    }
+
+    impl<T: From<u8>> Default for Limits<T> {
+        fn default() -> Self {
+            Self { size: 1, timeout: T::from(30) }
+        }
+    }
+
+    let limits = Limits::<u64>::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(limits, Limits { size: 8, timeout: 30 });
+}
+
+#[test]
+fn generic_metadata_describes_the_parameters() {
+    let keys = Pool::<Postgres>::cli_definition()
+        .keys
+        .into_iter()
+        .map(|flag| flag.key)
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["size", "settings.url", "settings.port"]);
+}
+
+#[test]
+fn generic_enums_load_their_parameters() {
+    let value = cruct::ConfigValue::Section(
+        [("Right".to_string(), cruct::ConfigValue::Value("7".into()))].into(),
+    );
+
+    assert_eq!(Either::<String, u8>::from_config_value(&value).unwrap(), Either::Right(7));
+}
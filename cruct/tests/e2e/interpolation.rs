@@ -0,0 +1,93 @@
+use assay::assay;
+use cruct::{ParserError, cruct};
+
+#[assay(
+    include = ["tests/fixtures/e2e/interpolation.toml"],
+    env = [
+        ("DB_HOST", "db.internal"),
+    ]
+)]
+fn placeholders_are_expanded_from_env() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/interpolation.toml"), interpolate = true)]
+    #[derive(Debug)]
+    struct Interpolated {
+        database_url: String,
+        port: u16,
+    }
+
+    let cfg = Interpolated::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.database_url, "postgres://db.internal/app");
+    assert_eq!(cfg.port, 8080);
+}
+
+#[assay(
+    include = ["tests/fixtures/e2e/interpolation.toml"],
+)]
+fn unresolved_placeholders_are_reported() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/interpolation.toml"), interpolate = true)]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Interpolated {
+        database_url: String,
+    }
+
+    unsafe {
+        std::env::remove_var("DB_HOST");
+    }
+
+    let err = Interpolated::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert!(
+        matches!(err, ParserError::UnresolvedVariables(names) if names == ["database_url -> DB_HOST"])
+    );
+}
+
+#[assay(
+    include = ["tests/fixtures/e2e/interpolation.toml"],
+)]
+fn placeholders_are_kept_without_opt_in() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/interpolation.toml"))]
+    #[derive(Debug)]
+    struct Raw {
+        database_url: String,
+    }
+
+    let cfg = Raw::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.database_url, "postgres://${DB_HOST}/app");
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/references.toml",
+        "tests/fixtures/e2e/references_override.toml",
+    ],
+)]
+fn references_see_overrides_from_other_sources() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/references.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/references_override.toml", priority = 0),
+        interpolate = true
+    )]
+    #[derive(Debug)]
+    struct Paths {
+        log_dir: String,
+    }
+
+    let cfg = Paths::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.log_dir, "/opt/app/logs");
+}
@@ -0,0 +1,201 @@
+use cruct::cruct;
+
+#[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+#[allow(dead_code)]
+struct App {
+    /// The port to listen on.
+    #[field(name = "port", default = 8080)]
+    http_port: u16,
+
+    #[field(optional = true)]
+    motd: Option<String>,
+
+    database: Database,
+}
+
+#[cruct]
+#[allow(dead_code)]
+struct Database {
+    /// The connection string.
+    url: String,
+
+    #[field(default = Vec::new())]
+    replicas: Vec<String>,
+}
+
+#[test]
+fn schema_describes_the_struct() {
+    let schema = App::json_schema();
+
+    assert!(schema.contains(r#""$schema": "https://json-schema.org/draft/2020-12/schema""#));
+    assert!(schema.contains(r#""title": "App""#));
+    assert!(schema.contains(
+        r#""port": {
+      "type": "integer",
+      "minimum": 0,
+      "description": "The port to listen on.",
+      "default": 8080
+    }"#
+    ));
+    assert!(schema.contains(
+        r#""required": [
+    "database"
+  ]"#
+    ));
+}
+
+#[test]
+fn schema_describes_nested_structs() {
+    let schema = App::json_schema();
+
+    assert!(schema.contains(
+        r##""database": {
+      "$ref": "#/$defs/Database"
+    }"##
+    ));
+    assert!(schema.contains(
+        r#""Database": {
+      "type": "object",
+      "properties": {
+        "url": {
+          "type": "string",
+          "description": "The connection string."
+        },
+        "replicas": {
+          "type": "array",
+          "items": {
+            "type": "string"
+          }
+        }
+      },
+      "required": [
+        "url"
+      ]
+    }"#
+    ));
+}
+
+#[cruct]
+#[allow(dead_code)]
+struct Port(u16);
+
+#[cruct]
+#[allow(dead_code)]
+struct Window(u32, u32);
+
+#[cruct]
+#[allow(dead_code)]
+struct Logging {
+    #[field(default = false)]
+    verbose: bool,
+
+    level: Option<String>,
+}
+
+#[cruct(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum Backend {
+    S3 { bucket: String },
+    Local(String),
+    Memory,
+}
+
+#[cruct(tag = "type", rename_all = "lowercase")]
+#[allow(dead_code)]
+enum Storage {
+    S3 { bucket: String },
+    Memory,
+}
+
+#[cruct]
+#[allow(dead_code)]
+struct Service {
+    port: Port,
+    window: Window,
+    logging: Logging,
+    backend: Backend,
+    storage: Storage,
+}
+
+#[test]
+fn schema_describes_newtypes_as_their_values() {
+    let schema = Service::json_schema();
+
+    assert!(schema.contains(
+        r#""port": {
+      "type": "integer",
+      "minimum": 0
+    }"#
+    ));
+    assert!(schema.contains(
+        r#""window": {
+      "type": "array",
+      "prefixItems": [
+        {
+          "type": "integer",
+          "minimum": 0
+        },
+        {
+          "type": "integer",
+          "minimum": 0
+        }
+      ],
+      "minItems": 2,
+      "maxItems": 2
+    }"#
+    ));
+}
+
+#[test]
+fn schema_describes_the_variants_of_enums() {
+    let schema = Service::json_schema();
+
+    assert!(schema.contains(
+        r#""local": {
+              "type": "string"
+            }
+          },
+          "required": [
+            "local"
+          ],
+          "additionalProperties": false
+        },
+        {
+          "const": "memory"
+        }
+      ]"#
+    ));
+    assert!(schema.contains(
+        r#""storage": {
+      "oneOf": [
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "s3"
+            },
+            "bucket": {
+              "type": "string"
+            }
+          },
+          "required": [
+            "type",
+            "bucket"
+          ]
+        },"#
+    ));
+}
+
+#[test]
+fn schema_only_requires_sections_with_required_fields() {
+    let schema = Service::json_schema();
+
+    assert!(schema.contains(
+        r#""required": [
+    "port",
+    "window",
+    "backend",
+    "storage"
+  ]"#
+    ));
+}
@@ -0,0 +1,31 @@
+use cruct::cruct;
+
+#[cruct]
+#[allow(dead_code)]
+struct App {
+    /// The port to listen on.
+    #[field(default = 8080, env_override = "APP_PORT", arg_override = "port")]
+    http_port: u16,
+
+    database: Database,
+}
+
+#[cruct]
+#[allow(dead_code)]
+struct Database {
+    #[field(description = "The connection string.")]
+    url: String,
+}
+
+#[test]
+fn reference_documents_nested_keys() {
+    let reference = App::markdown_reference();
+
+    assert!(reference.starts_with("| Key | Type | Default | Env | Flag | Description |\n"));
+    assert!(reference.contains(
+        "| `http_port` | `u16` | `8080` | `APP_PORT` | `--port` | The port to listen on. |"
+    ));
+    assert!(reference.contains(
+        "| `database.url` | `String` | *required* |  | `--database.url` | The connection string. |"
+    ));
+}
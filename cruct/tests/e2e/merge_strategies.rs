@@ -0,0 +1,104 @@
+use assay::assay;
+use cruct::cruct;
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/merge/base.toml",
+        "tests/fixtures/e2e/merge/overlay.toml",
+    ],
+)]
+fn arrays_follow_field_merge_strategies() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/merge/base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/merge/overlay.toml", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Service {
+        #[field(merge = "append")]
+        plugins: Vec<String>,
+
+        hosts: Vec<String>,
+
+        #[field(merge = "by_key(name)")]
+        upstreams: Vec<Upstream>,
+
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[derive(Debug, PartialEq)]
+    struct Upstream {
+        name: String,
+        port: u16,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    struct Nested {
+        #[field(merge = "prepend")]
+        tags: Vec<String>,
+    }
+
+    let service = Service::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(service.plugins, ["auth", "metrics", "tracing"]);
+    assert_eq!(service.hosts, ["b.internal"]);
+    assert_eq!(
+        service.upstreams,
+        [
+            Upstream { name: "primary".into(), port: 80 },
+            Upstream { name: "replica".into(), port: 9000 },
+            Upstream { name: "backup".into(), port: 82 },
+        ]
+    );
+    assert_eq!(
+        service
+            .nested
+            .tags,
+        ["overlay", "base"]
+    );
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/merge/alias_base.toml",
+        "tests/fixtures/e2e/merge/alias_overlay.toml",
+    ],
+)]
+fn aliases_follow_field_merge_strategies() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/merge/alias_base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/merge/alias_overlay.toml", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Service {
+        #[field(merge = "append", alias = "extensions")]
+        plugins: Vec<String>,
+
+        #[field(alias = "inner")]
+        nested: Nested,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    struct Nested {
+        #[field(merge = "prepend")]
+        tags: Vec<String>,
+    }
+
+    let service = Service::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(service.plugins, ["auth", "metrics", "tracing"]);
+    assert_eq!(
+        service
+            .nested
+            .tags,
+        ["overlay", "base"]
+    );
+}
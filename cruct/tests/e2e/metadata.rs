@@ -0,0 +1,82 @@
+use cruct::{DefaultValue, MergeStrategy, cruct};
+
+#[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+#[allow(dead_code)]
+struct App {
+    /// The port to listen on.
+    #[field(default = 8080, arg_override = "port", env_override = "APP_PORT")]
+    http_port: u16,
+
+    /// Ignored in favour of the explicit description.
+    #[field(description = "The ratio of requests sampled.", default = -0.5)]
+    sample_rate: f64,
+
+    #[field(optional = true)]
+    motd: Option<String>,
+
+    #[field(merge = "append", default = Vec::new())]
+    tags: Vec<String>,
+
+    /// The upstream servers.
+    upstreams: Vec<Upstream>,
+}
+
+#[cruct]
+#[allow(dead_code)]
+struct Upstream {
+    #[field(name = "address", default = "localhost".to_string())]
+    host: String,
+
+    /// Whether TLS is enabled.
+    #[field(default = true)]
+    tls: bool,
+}
+
+#[test]
+fn metadata_describes_each_field() {
+    let fields = App::field_metadata();
+
+    let keys = fields
+        .iter()
+        .map(|field| field.key)
+        .collect::<Vec<_>>();
+    assert_eq!(keys, ["http_port", "sample_rate", "motd", "tags", "upstreams"]);
+
+    let port = &fields[0];
+    assert_eq!(port.ty, "u16");
+    assert_eq!(port.default, Some(DefaultValue::Integer(8080)));
+    assert_eq!(port.env, Some("APP_PORT"));
+    assert_eq!(port.arg, Some("port"));
+    assert_eq!(port.description, Some("The port to listen on."));
+    assert!(!port.is_required());
+
+    let rate = &fields[1];
+    assert_eq!(rate.default, Some(DefaultValue::Float(-0.5)));
+    assert_eq!(rate.description, Some("The ratio of requests sampled."));
+
+    let motd = &fields[2];
+    assert!(motd.optional);
+    assert_eq!(motd.description, None);
+
+    let tags = &fields[3];
+    assert_eq!(tags.merge, Some(MergeStrategy::Append));
+    assert_eq!(tags.default, Some(DefaultValue::Expr("Vec::new()")));
+}
+
+#[test]
+fn metadata_includes_nested_fields() {
+    let fields = App::field_metadata();
+    let upstreams = &fields[4];
+
+    assert_eq!(upstreams.ty, "Vec<Upstream>");
+    assert!(upstreams.is_required());
+
+    let host = &upstreams.fields[0];
+    assert_eq!(host.name, "host");
+    assert_eq!(host.key, "address");
+    assert_eq!(host.default, Some(DefaultValue::Str("localhost")));
+
+    let tls = &upstreams.fields[1];
+    assert_eq!(tls.default, Some(DefaultValue::Bool(true)));
+    assert_eq!(tls.description, Some("Whether TLS is enabled."));
+}
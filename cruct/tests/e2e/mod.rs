@@ -1,7 +1,24 @@
-// TODO: figure out how to run this test
-// mod cli_overrides;
+mod aliases;
+mod cli_help;
+mod cli_overrides;
 mod default_values;
+mod enums;
 mod env_overrides;
+mod example_config;
 mod flat_nested;
+mod generics;
+mod interpolation;
+mod json_schema;
+mod markdown_reference;
+mod merge_strategies;
+mod metadata;
 mod nested_structs;
+mod newtypes;
 mod optional;
+mod patches;
+mod profiles;
+mod rename_all;
+mod struct_validation;
+mod unknown_keys;
+mod unset;
+mod validation;
@@ -0,0 +1,104 @@
+use assay::assay;
+use cruct::{ParserError, cruct};
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Hostname(String);
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Coordinates(f64, f64);
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Credentials {
+    user: String,
+
+    #[field(default = "changeme".to_string())]
+    password: String,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Database(Credentials);
+
+#[assay(include = ["tests/fixtures/e2e/newtypes/config.toml"])]
+fn newtypes_read_the_wrapped_value() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/newtypes/config.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        port: Port,
+        host: Hostname,
+        mirrors: Vec<Hostname>,
+        origin: Coordinates,
+        database: Database,
+
+        #[field(optional = true)]
+        fallback: Option<Port>,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.port, Port(8080));
+    assert_eq!(cfg.host, Hostname("db.internal".into()));
+    assert_eq!(cfg.mirrors, [Hostname("a.internal".into()), Hostname("b.internal".into())]);
+    assert_eq!(cfg.origin, Coordinates(51.5, -0.12));
+    assert_eq!(
+        cfg.database,
+        Database(Credentials {
+            user: "admin".into(),
+            password: "changeme".into()
+        })
+    );
+    assert_eq!(cfg.fallback, None);
+}
+
+#[test]
+fn newtypes_forward_nested_keys() {
+    #[cruct]
+    #[allow(dead_code)]
+    struct Config {
+        port: Port,
+        database: Database,
+    }
+
+    let keys = Config::cli_definition()
+        .keys
+        .into_iter()
+        .map(|flag| flag.key)
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["port", "database.user", "database.password"]);
+}
+
+#[assay(include = ["tests/fixtures/e2e/newtypes/invalid.toml"])]
+fn tuple_structs_require_every_item() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/newtypes/invalid.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        origin: Coordinates,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    let ParserError::NestedError { section, source } = err else {
+        panic!("expected a nested error, found {err}");
+    };
+
+    assert_eq!(section, "origin");
+    assert!(matches!(
+        *source,
+        ParserError::TypeMismatch { expected, .. } if expected == "array of 2 items"
+    ));
+}
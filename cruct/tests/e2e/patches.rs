@@ -0,0 +1,64 @@
+use assay::assay;
+use cruct::{JsonPatchSource, MergePatchSource, ParserError, cruct};
+
+#[cruct(load_config(path = "tests/fixtures/e2e/patch/base.toml"))]
+#[derive(Debug)]
+struct Service {
+    plugins: Vec<String>,
+    server: Server,
+}
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+
+    #[field(default = 1)]
+    workers: u32,
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/patch/base.toml",
+        "tests/fixtures/e2e/patch/merge.json",
+        "tests/fixtures/e2e/patch/patch.json",
+    ],
+)]
+fn patches_apply_over_config_files() {
+    let service = Service::loader()
+        .with_config()
+        .with_source(MergePatchSource::new("tests/fixtures/e2e/patch/merge.json", 1))
+        .with_source(JsonPatchSource::new("tests/fixtures/e2e/patch/patch.json", 0))
+        .load()
+        .unwrap();
+
+    assert_eq!(service.plugins, ["auth", "metrics", "tracing"]);
+    assert_eq!(
+        service.server,
+        Server {
+            host: "127.0.0.1".into(),
+            port: 8080,
+            workers: 1,
+        }
+    );
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/patch/base.toml",
+        "tests/fixtures/e2e/patch/failing.yml",
+    ],
+)]
+fn failed_test_operation_is_reported() {
+    let err = Service::loader()
+        .with_config()
+        .with_source(JsonPatchSource::new("tests/fixtures/e2e/patch/failing.yml", 0))
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        ParserError::PatchFailed { op, path, .. } if op == "test" && path == "/server/port"
+    ));
+}
@@ -0,0 +1,84 @@
+use assay::assay;
+use cruct::cruct;
+
+#[assay(
+    include = ["tests/fixtures/e2e/profiles/app.toml"],
+    env = [
+        ("APP_PROFILE", "prod"),
+    ]
+)]
+fn profile_is_selected_from_env() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/profiles/app.toml", profiles = true),
+        profile_env = "APP_PROFILE"
+    )]
+    #[derive(Debug)]
+    struct App {
+        host: String,
+        port: u16,
+    }
+
+    let loader = App::loader().with_config();
+    assert_eq!(
+        loader
+            .active_profile()
+            .as_deref(),
+        Some("prod")
+    );
+
+    let app = loader
+        .load()
+        .unwrap();
+
+    assert_eq!(app.host, "example.com");
+    assert_eq!(app.port, 8080);
+}
+
+#[assay(
+    include = ["tests/fixtures/e2e/profiles/app.toml"],
+)]
+fn default_profile_is_used_when_env_is_unset() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/profiles/app.toml", profiles = true),
+        profile_env = "APP_PROFILE"
+    )]
+    #[derive(Debug)]
+    struct App {
+        host: String,
+    }
+
+    unsafe {
+        std::env::remove_var("APP_PROFILE");
+    }
+
+    let app = App::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(app.host, "localhost");
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/profiles/overlay.toml",
+        "tests/fixtures/e2e/profiles/overlay.prod.toml",
+    ],
+)]
+fn explicit_profile_applies_overlay_file() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/profiles/overlay.toml"))]
+    #[derive(Debug)]
+    struct App {
+        host: String,
+        port: u16,
+    }
+
+    let app = App::loader()
+        .with_config()
+        .with_profile("prod")
+        .load()
+        .unwrap();
+
+    assert_eq!(app.host, "localhost");
+    assert_eq!(app.port, 443);
+}
@@ -0,0 +1,98 @@
+use assay::assay;
+use cruct::cruct;
+
+#[cruct(rename_all = "kebab-case")]
+#[derive(Debug, PartialEq)]
+struct TlsSettings {
+    cert_path: String,
+}
+
+#[assay(include = ["tests/fixtures/e2e/rename/kebab.yml"])]
+fn kebab_case_keys() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/rename/kebab.yml"), rename_all = "kebab-case")]
+    #[derive(Debug)]
+    struct Config {
+        http_port: u16,
+        max_connections: u32,
+        database_url: String,
+        tls_settings: TlsSettings,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 8080);
+    assert_eq!(cfg.max_connections, 32);
+    assert_eq!(cfg.database_url, "postgres://localhost");
+    assert_eq!(cfg.tls_settings, TlsSettings { cert_path: "/etc/tls/cert.pem".into() });
+}
+
+#[assay(include = ["tests/fixtures/e2e/rename/camel.json"])]
+fn camel_case_keys_with_explicit_names() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/rename/camel.json"), rename_all = "camelCase")]
+    #[derive(Debug)]
+    struct Config {
+        http_port: u16,
+
+        #[field(name = "max_conns")]
+        max_connections: u32,
+
+        database_url: String,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 9090);
+    assert_eq!(cfg.max_connections, 16);
+    assert_eq!(cfg.database_url, "postgres://db");
+}
+
+#[test]
+fn env_prefix_derives_variable_names() {
+    #[cruct(rename_all = "kebab-case", env_prefix = "APP_")]
+    #[derive(Debug)]
+    struct Config {
+        http_port: u16,
+
+        #[field(env_override = "DATABASE_URL")]
+        database_url: String,
+    }
+
+    let cfg = Config::loader()
+        .with_env_provider(|name| match name {
+            "APP_HTTP_PORT" => Some("7070".into()),
+            "DATABASE_URL" => Some("postgres://env".into()),
+            _ => None,
+        })
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 7070);
+    assert_eq!(cfg.database_url, "postgres://env");
+
+    let fields = Config::field_metadata();
+    assert_eq!(fields[0].key, "http-port");
+    assert_eq!(fields[0].env, Some("APP_HTTP_PORT"));
+}
+
+#[test]
+fn rename_all_derives_variable_names_without_a_prefix() {
+    #[cruct(rename_all = "camelCase")]
+    #[derive(Debug)]
+    struct Config {
+        http_port: u16,
+    }
+
+    let cfg = Config::loader()
+        .with_env_provider(|name| (name == "HTTP_PORT").then(|| "6060".into()))
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.http_port, 6060);
+    assert_eq!(Config::field_metadata()[0].env, Some("HTTP_PORT"));
+}
@@ -0,0 +1,85 @@
+use assay::assay;
+use cruct::cruct;
+
+#[cruct(validate = Self::check)]
+#[derive(Debug)]
+struct Tls {
+    enabled: bool,
+
+    #[field(optional = true)]
+    cert: Option<String>,
+}
+
+impl Tls {
+    fn check(&self) -> Result<(), String> {
+        match self.enabled
+            && self
+                .cert
+                .is_none()
+        {
+            true => Err("`cert` is required when TLS is enabled".into()),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cruct(validate = Self::check)]
+#[derive(Debug)]
+struct Config {
+    min_connections: u32,
+    max_connections: u32,
+    tls: Tls,
+}
+
+impl Config {
+    fn check(&self) -> Result<(), String> {
+        match self.min_connections > self.max_connections {
+            true => Err(format!(
+                "min_connections ({}) must not exceed max_connections ({})",
+                self.min_connections, self.max_connections
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Config, cruct::Error> {
+    Config::loader()
+        .with_source(cruct::ConfigFileSource::new(path, None))
+        .load()
+}
+
+#[assay(include = ["tests/fixtures/e2e/struct_validation/valid.toml"])]
+fn valid_structs_load() {
+    let cfg = load("tests/fixtures/e2e/struct_validation/valid.toml").unwrap();
+
+    assert_eq!(cfg.min_connections, 1);
+    assert_eq!(cfg.max_connections, 10);
+    assert_eq!(
+        cfg.tls
+            .cert
+            .as_deref(),
+        Some("server.pem")
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/struct_validation/pool.toml"])]
+fn root_validation_errors_are_reported() {
+    let error = load("tests/fixtures/e2e/struct_validation/pool.toml").unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Invalid configuration: min_connections (10) must not exceed max_connections (5)"
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/struct_validation/tls.toml"])]
+fn nested_validation_errors_hold_the_section_path() {
+    let error = load("tests/fixtures/e2e/struct_validation/tls.toml").unwrap_err();
+
+    assert!(matches!(
+        &error,
+        cruct::Error::InvalidStruct { path, .. } if path == "tls"
+    ));
+    assert_eq!(error.to_string(), "Invalid section 'tls': `cert` is required when TLS is enabled");
+}
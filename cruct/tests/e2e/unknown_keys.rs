@@ -0,0 +1,164 @@
+use assay::assay;
+use cruct::{ConfigWarning, cruct};
+
+#[cruct]
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Database {
+    url: String,
+
+    #[field(default = 1)]
+    pool_size: u32,
+}
+
+#[assay(include = ["tests/fixtures/e2e/unknown_keys/typos.toml"])]
+fn unknown_keys_are_reported_as_warnings() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/unknown_keys/typos.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        name: String,
+
+        #[field(default = 80)]
+        http_port: u16,
+
+        database: Database,
+    }
+
+    let (cfg, warnings) = Config::loader()
+        .with_config()
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(cfg.name, "app");
+    assert_eq!(cfg.http_port, 80);
+    assert_eq!(
+        cfg.database
+            .pool_size,
+        1
+    );
+
+    assert_eq!(
+        warnings,
+        [
+            ConfigWarning::UnknownKey {
+                key: "database.pool_sise".into(),
+                suggestion: Some("pool_size".into()),
+            },
+            ConfigWarning::UnknownKey {
+                key: "completely_unrelated".into(),
+                suggestion: None,
+            },
+            ConfigWarning::UnknownKey {
+                key: "htpp_port".into(),
+                suggestion: Some("http_port".into()),
+            },
+        ]
+    );
+    assert_eq!(warnings[2].to_string(), "Unknown key 'htpp_port', did you mean `http_port`?");
+}
+
+#[assay(include = ["tests/fixtures/e2e/unknown_keys/typos.toml"])]
+fn unknown_keys_are_denied() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/unknown_keys/typos.toml"), deny_unknown_fields)]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+
+        #[field(default = 80)]
+        http_port: u16,
+
+        database: Database,
+    }
+
+    let error = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Unknown keys: 'completely_unrelated', 'database.pool_sise' (did you mean `pool_size`?), \
+         'htpp_port' (did you mean `http_port`?)"
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/unknown_keys/typos.toml"])]
+fn unknown_keys_are_denied_in_nested_sections() {
+    #[cruct(deny_unknown_fields)]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct StrictDatabase {
+        url: String,
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/unknown_keys/typos.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+        database: StrictDatabase,
+    }
+
+    let error = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "Unknown keys: 'database.pool_sise'");
+}
+
+#[assay(include = ["tests/fixtures/e2e/unknown_keys/flat.toml"])]
+fn flattened_keys_are_known() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/unknown_keys/flat.toml"), deny_unknown_fields)]
+    #[derive(Debug)]
+    struct Config {
+        name: String,
+
+        #[field(flatten)]
+        database: Database,
+    }
+
+    let (cfg, warnings) = Config::loader()
+        .with_config()
+        .load_with_warnings()
+        .unwrap();
+
+    assert_eq!(cfg.name, "app");
+    assert_eq!(
+        cfg.database
+            .pool_size,
+        5
+    );
+    assert!(warnings.is_empty());
+}
+
+#[assay(include = ["tests/fixtures/e2e/unknown_keys/flat_typos.toml"])]
+fn unknown_keys_are_denied_by_flattened_structs() {
+    #[cruct(deny_unknown_fields)]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct StrictDatabase {
+        url: String,
+
+        #[field(default = 1)]
+        pool_size: u32,
+    }
+
+    #[cruct(load_config(path = "tests/fixtures/e2e/unknown_keys/flat_typos.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        name: String,
+
+        #[field(flatten)]
+        database: StrictDatabase,
+    }
+
+    let error = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "Unknown keys: 'pool_sise' (did you mean `pool_size`?)");
+}
@@ -0,0 +1,123 @@
+use assay::assay;
+use cruct::{ParserError, cruct};
+
+#[cruct]
+#[derive(Debug, PartialEq)]
+struct Pool {
+    size: u32,
+
+    #[field(default = 60)]
+    timeout: u32,
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/unset/base.toml",
+        "tests/fixtures/e2e/unset/overlay.json",
+    ],
+)]
+fn json_null_unsets_inherited_keys() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/unset/base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/unset/overlay.json", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Database {
+        host: String,
+
+        #[field(default = 3306)]
+        port: u16,
+
+        pool: Pool,
+    }
+
+    let db = Database::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(db.host, "db.internal");
+    assert_eq!(db.port, 3306);
+    assert_eq!(db.pool, Pool { size: 10, timeout: 60 });
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/unset/base.toml",
+        "tests/fixtures/e2e/unset/overlay.yml",
+    ],
+)]
+fn yaml_null_unsets_inherited_keys() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/unset/base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/unset/overlay.yml", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Database {
+        #[field(default = 3306)]
+        port: u16,
+
+        pool: Pool,
+    }
+
+    let db = Database::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(db.port, 3306);
+    assert_eq!(db.pool, Pool { size: 10, timeout: 60 });
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/unset/base.toml",
+        "tests/fixtures/e2e/unset/tagged.yml",
+    ],
+)]
+fn yaml_unset_tag_unsets_inherited_keys() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/unset/base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/unset/tagged.yml", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Database {
+        #[field(optional = true)]
+        port: Option<u16>,
+
+        pool: Pool,
+    }
+
+    let db = Database::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(db.port, None);
+    assert_eq!(db.pool, Pool { size: 10, timeout: 60 });
+}
+
+#[assay(
+    include = [
+        "tests/fixtures/e2e/unset/base.toml",
+        "tests/fixtures/e2e/unset/required.json",
+    ],
+)]
+fn unset_required_field_is_missing() {
+    #[cruct(
+        load_config(path = "tests/fixtures/e2e/unset/base.toml", priority = 1),
+        load_config(path = "tests/fixtures/e2e/unset/required.json", priority = 0)
+    )]
+    #[derive(Debug)]
+    struct Database {
+        #[allow(dead_code)]
+        user: String,
+    }
+
+    let err = Database::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(err, ParserError::MissingField(field) if field == "user"));
+}
@@ -0,0 +1,188 @@
+use assay::assay;
+use cruct::cruct;
+
+/// Rejects pools that could not serve a single connection.
+fn non_empty_pool(size: &u32) -> Result<(), String> {
+    match *size {
+        0 => Err("the pool cannot be empty".into()),
+        _ => Ok(()),
+    }
+}
+
+#[cruct]
+#[derive(Debug, Default)]
+struct Database {
+    #[field(validate = non_empty_pool)]
+    pool_size: u32,
+}
+
+#[assay(include = ["tests/fixtures/e2e/validation/valid.toml"])]
+fn valid_fields_load() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/validation/valid.toml"))]
+    #[derive(Debug)]
+    struct Config {
+        name: String,
+
+        #[field(range(min = 1, max = 65535))]
+        port: u16,
+
+        #[field(one_of = ["debug", "info"])]
+        level: String,
+
+        #[field(length(min = 1, max = 4))]
+        tags: Vec<String>,
+
+        #[field(optional = true, range(max = 10))]
+        retries: Option<u32>,
+
+        database: Database,
+    }
+
+    let cfg = Config::loader()
+        .with_config()
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg.name, "app");
+    assert_eq!(cfg.port, 8080);
+    assert_eq!(cfg.level, "info");
+    assert_eq!(cfg.tags, ["web"]);
+    assert_eq!(cfg.retries, None);
+    assert_eq!(
+        cfg.database
+            .pool_size,
+        10
+    );
+}
+
+#[assay(include = ["tests/fixtures/e2e/validation/invalid.toml"])]
+fn invalid_fields_are_reported() {
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Port {
+        #[field(range(min = 1, max = 65535))]
+        port: u16,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Level {
+        #[field(one_of = ["debug", "info"])]
+        level: String,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Tags {
+        #[field(length(min = 1))]
+        tags: Vec<String>,
+    }
+
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Pool {
+        database: Database,
+    }
+
+    let config = cruct::ConfigFileSource::new("tests/fixtures/e2e/validation/invalid.toml", None);
+    let config = cruct::ConfigSource::load(&config).unwrap();
+
+    assert_eq!(
+        Port::load_from(&config)
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for 'port': must be between 1 and 65535, found 0"
+    );
+    assert_eq!(
+        Level::load_from(&config)
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for 'level': must be one of `debug`, `info`, found 'trace'"
+    );
+    assert_eq!(
+        Tags::load_from(&config)
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for 'tags': length must be at least 1, found 0"
+    );
+    assert_eq!(
+        Pool::load_from(&config)
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for 'database.pool_size': the pool cannot be empty"
+    );
+}
+
+#[cfg(feature = "regex")]
+#[assay(include = ["tests/fixtures/e2e/validation/valid.toml", "tests/fixtures/e2e/validation/invalid.toml"])]
+fn patterns_are_checked() {
+    #[cruct]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Name {
+        #[field(pattern = "^[a-z]+$")]
+        name: String,
+    }
+
+    for (path, expected) in [
+        ("tests/fixtures/e2e/validation/valid.toml", None),
+        (
+            "tests/fixtures/e2e/validation/invalid.toml",
+            Some("Invalid value for 'name': must match the pattern `^[a-z]+$`, found 'App'"),
+        ),
+    ] {
+        let config = cruct::ConfigFileSource::new(path, None);
+        let config = cruct::ConfigSource::load(&config).unwrap();
+
+        // Loaded twice, so that the compiled pattern is reused.
+        for _ in 0..2 {
+            let result = Name::load_from(&config);
+            assert_eq!(
+                result
+                    .err()
+                    .map(|e| e.to_string())
+                    .as_deref(),
+                expected
+            );
+        }
+    }
+}
+
+#[assay(include = ["tests/fixtures/e2e/validation/invalid.toml"])]
+fn optional_sections_keep_validation_errors() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/validation/invalid.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        #[field(optional = true)]
+        database: Option<Database>,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "Invalid value for 'database.pool_size': the pool cannot be empty");
+}
+
+#[assay(include = ["tests/fixtures/e2e/validation/invalid.toml"])]
+fn defaulted_sections_keep_validation_errors() {
+    #[cruct(load_config(path = "tests/fixtures/e2e/validation/invalid.toml"), default)]
+    #[derive(Debug, Default)]
+    #[allow(dead_code)]
+    struct Config {
+        database: Database,
+    }
+
+    let err = Config::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "Invalid value for 'database.pool_size': the pool cannot be empty");
+}
@@ -114,3 +114,20 @@ fn test_nested_array_yaml() {
 
     assert_eq!(cfg.matrix, vec![vec![1, 2], vec![3, 4]]);
 }
+
+#[test]
+fn test_scalar_is_not_an_array() {
+    #[cruct(load_config(path = "tests/fixtures/test_config.toml"))]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct ScalarArray {
+        name: Vec<String>,
+    }
+
+    let err = ScalarArray::loader()
+        .with_config()
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(err, cruct::ParserError::TypeMismatch { field, .. } if field == "name"));
+}
@@ -10,6 +10,7 @@ fn missing_flat_nested_field() {
     #[allow(dead_code)]
     struct Config {
         exists: String,
+        #[field(flatten)]
         nested: Nested,
     }
 
@@ -41,6 +42,7 @@ fn flat_nested_type_mismatch() {
     #[derive(Debug)]
     #[allow(dead_code)]
     struct Config {
+        #[field(flatten)]
         nested: Nested,
     }
 
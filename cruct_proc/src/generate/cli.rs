@@ -0,0 +1,74 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::generate::metadata::{field_default_tokens, option_tokens, type_name};
+use crate::parse::StructField;
+
+/// Generates the body of `FromConfigValue::cli_flags` for a struct, pushing
+/// one flag per field with an `arg_override` into a `flags` vector in scope.
+///
+/// Other fields contribute the flags of the nested `#[cruct]` struct they
+/// hold, if any, nested under the field key unless the field is flattened.
+pub fn generate_cli_flags(fields: &[StructField]) -> TokenStream {
+    let flags = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            let key = field.config_key();
+
+            if field.is_flattened() {
+                return quote! {
+                    flags.extend(<#ty as ::cruct::FromConfigValue>::cli_flags());
+                };
+            }
+            let ty_name = type_name(ty);
+
+            let params = field
+                .params
+                .as_ref();
+
+            let Some(arg) = params.and_then(|p| {
+                p.arg_override
+                    .as_ref()
+            }) else {
+                return quote! {
+                    flags.extend(
+                        <#ty as ::cruct::FromConfigValue>::cli_flags()
+                            .iter()
+                            .map(|flag| flag.nested(#key))
+                    );
+                };
+            };
+            let env = owned_tokens(params.and_then(|p| {
+                p.env_override
+                    .as_ref()
+            }));
+            let default = match field_default_tokens(field) {
+                Some(default) => quote! { Some(#default.to_string()) },
+                None => quote! { None },
+            };
+            let description = owned_tokens(
+                field
+                    .description
+                    .as_ref(),
+            );
+
+            quote! {
+                flags.push(::cruct::CliFlag {
+                    key: #key.to_string(),
+                    arg: Some(#arg.to_string()),
+                    ty: #ty_name.to_string(),
+                    default: #default,
+                    env: #env,
+                    description: #description,
+                });
+            }
+        });
+
+    quote! { #(#flags)* }
+}
+
+/// Converts an optional string into the tokens of an `Option<String>`.
+fn owned_tokens(value: Option<&String>) -> TokenStream {
+    option_tokens(value.map(|value| quote! { #value.to_string() }))
+}
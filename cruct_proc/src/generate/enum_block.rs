@@ -0,0 +1,361 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error as SynError, Generics, Ident, Type};
+
+use crate::generate::metadata::{option_tokens, value_metadata};
+use crate::generate::{
+    generate_field_initialization,
+    generate_field_metadata,
+    generate_flatten_check,
+    with_config_bounds,
+};
+use crate::parse::{EnumVariant, FieldParams, MacroParams, StructField, VariantKind};
+
+/// Generate the implementation of `FromConfigValue` for an enum annotated
+/// with `#[cruct]`.
+///
+/// Enums are externally tagged by default: unit variants are written as
+/// their name, such as `mode = "primary"`, and variants holding data as a
+/// section with a single key naming the variant, such as
+/// `backend = { s3 = { bucket = "x" } }`.
+///
+/// With `tag = "type"`, enums are internally tagged instead: the variant is
+/// named by the `type` key of a section holding its fields, such as
+/// `backend = { type = "s3", bucket = "x" }`.
+pub fn generate_enum_block(
+    enum_name: &Ident,
+    generics: &Generics,
+    params: &MacroParams,
+    variants: &[EnumVariant],
+) -> TokenStream {
+    let enum_str = enum_name.to_string();
+
+    let generics = with_config_bounds(generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let names = variants
+        .iter()
+        .map(|variant| &variant.name)
+        .collect::<Vec<_>>();
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let name = &variant.name;
+
+            let condition = if params.insensitive {
+                quote! { name.eq_ignore_ascii_case(#name) }
+            } else {
+                quote! { name == #name }
+            };
+
+            let build = match &params.tag {
+                Some(_) => build_internal(variant),
+                None => build_external(variant),
+            };
+
+            quote! {
+                if #condition {
+                    return #build;
+                }
+            }
+        });
+
+    let select = match &params.tag {
+        Some(tag) => quote! {
+            let mut map = match value {
+                ConfigValue::Section(map) => map.clone(),
+                // Unit variants can be written as their name alone.
+                ConfigValue::Value(_) => ::std::collections::HashMap::from([
+                    (#tag.to_string(), value.clone()),
+                ]),
+                _ => {
+                    return Err(ParserError::TypeMismatch {
+                        field: #enum_str.into(),
+                        expected: "section".into(),
+                        found: value.to_string(),
+                    })
+                }
+            };
+
+            let name = match map.remove(#tag) {
+                Some(ConfigValue::Value(name)) => name,
+                Some(other) => {
+                    return Err(ParserError::TypeMismatch {
+                        field: #tag.into(),
+                        expected: "variant name".into(),
+                        found: other.to_string(),
+                    })
+                },
+                None => return Err(ParserError::MissingField(#tag.to_string())),
+            };
+            let name = name.as_str();
+        },
+        None => quote! {
+            #[allow(unused_variables)]
+            let (name, content) = match value {
+                ConfigValue::Value(name) => (name.as_str(), None),
+                ConfigValue::Section(map) if map.len() == 1 => {
+                    let (name, content) = map
+                        .iter()
+                        .next()
+                        .expect("the section holds a single key");
+                    (name.as_str(), Some(content))
+                },
+                _ => {
+                    return Err(ParserError::TypeMismatch {
+                        field: #enum_str.into(),
+                        expected: "variant name or section holding a single variant".into(),
+                        found: value.to_string(),
+                    })
+                }
+            };
+        },
+    };
+
+    let unit_only = variants
+        .iter()
+        .all(|variant| matches!(variant.kind, VariantKind::Unit));
+
+    let variant_names = unit_only.then(|| {
+        quote! {
+            fn variants() -> Vec<&'static str> {
+                vec![#(#names),*]
+            }
+        }
+    });
+
+    // Enums of unit variants are described by their names alone, unless
+    // their variant is named by a tag.
+    let type_metadata = (params
+        .tag
+        .is_some()
+        || !unit_only)
+        .then(|| {
+            let tag = option_tokens(
+                params
+                    .tag
+                    .as_ref(),
+            );
+            let variants = variants
+                .iter()
+                .map(variant_metadata);
+
+            quote! {
+                fn type_metadata() -> Option<::cruct::TypeMetadata> {
+                    Some(::cruct::TypeMetadata::Enum {
+                        tag: #tag,
+                        variants: vec![#(#variants),*],
+                    })
+                }
+            }
+        });
+
+    quote! {
+        impl #impl_generics ::cruct::FromConfigValue for #enum_name #ty_generics #where_clause {
+            fn from_config_value(
+                value: &::cruct::ConfigValue
+            ) -> Result<Self, ::cruct::ParserError> {
+                Self::from_config_value_with(value, &::cruct::LoadContext::from_process())
+            }
+
+            fn from_config_value_with(
+                value: &::cruct::ConfigValue,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                use ::cruct::{ConfigValue, ParserError};
+
+                #select
+                #(#arms)*
+
+                Err(ParserError::UnknownVariant {
+                    name: #enum_str.into(),
+                    found: name.to_string(),
+                    expected: vec![#(#names.to_string()),*],
+                })
+            }
+
+            #variant_names
+
+            #type_metadata
+        }
+    }
+}
+
+/// Generates the `VariantMetadata` describing a variant and the data it
+/// holds.
+fn variant_metadata(variant: &EnumVariant) -> TokenStream {
+    let name = &variant.name;
+
+    let content = match &variant.kind {
+        VariantKind::Unit => quote! { Unit },
+        VariantKind::Newtype(ty) => {
+            let metadata = value_metadata(name, ty);
+            quote! { Newtype(Box::new(#metadata)) }
+        },
+        VariantKind::Tuple(types) => {
+            let metadata = types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| value_metadata(&i.to_string(), ty));
+            quote! { Tuple(vec![#(#metadata),*]) }
+        },
+        VariantKind::Struct(fields) => {
+            let metadata = generate_field_metadata(fields);
+            quote! { Struct({ #metadata }) }
+        },
+    };
+
+    quote! {
+        ::cruct::VariantMetadata {
+            name: #name,
+            content: ::cruct::VariantContent::#content,
+        }
+    }
+}
+
+/// Generates the expression building a variant of an externally tagged enum
+/// from the `content` of its key, if any.
+fn build_external(variant: &EnumVariant) -> TokenStream {
+    let ident = &variant.ident;
+    let name = &variant.name;
+
+    match &variant.kind {
+        VariantKind::Unit => quote! { Ok(Self::#ident) },
+        VariantKind::Newtype(ty) => {
+            let parse = parse_value(ty, name, quote! { content });
+
+            quote! {
+                {
+                    let content = content
+                        .ok_or_else(|| ParserError::MissingField(#name.to_string()))?;
+                    Ok(Self::#ident(#parse))
+                }
+            }
+        },
+        VariantKind::Tuple(types) => {
+            let len = types.len();
+            let items = types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| parse_value(ty, name, quote! { &items[#i] }));
+
+            quote! {
+                match content {
+                    Some(ConfigValue::Array(items)) if items.len() == #len => {
+                        Ok(Self::#ident(#(#items),*))
+                    },
+                    _ => Err(ParserError::TypeMismatch {
+                        field: #name.into(),
+                        expected: format!("array of {} items", #len),
+                        found: content
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                    }),
+                }
+            }
+        },
+        VariantKind::Struct(fields) => {
+            let build = build_fields(ident, name, fields);
+
+            quote! {
+                {
+                    let mut map = match content {
+                        Some(ConfigValue::Section(map)) => map.clone(),
+                        None => ::std::collections::HashMap::new(),
+                        Some(other) => {
+                            return Err(ParserError::TypeMismatch {
+                                field: #name.into(),
+                                expected: "section".into(),
+                                found: other.to_string(),
+                            })
+                        }
+                    };
+
+                    let ctx = &ctx.nested(#name);
+
+                    #build
+                }
+            }
+        },
+    }
+}
+
+/// Generates the expression building a variant of an internally tagged enum
+/// from the remaining keys of its section, held in `map`.
+///
+/// The fields of struct variants are loaded in a context nested under the
+/// variant name, as with externally tagged enums, so that their paths read
+/// like `storage.s3.bucket`.
+fn build_internal(variant: &EnumVariant) -> TokenStream {
+    let ident = &variant.ident;
+    let name = &variant.name;
+
+    match &variant.kind {
+        VariantKind::Unit => quote! { Ok(Self::#ident) },
+        VariantKind::Newtype(ty) => {
+            let parse = parse_value(ty, name, quote! { &ConfigValue::Section(map) });
+            quote! { Ok(Self::#ident(#parse)) }
+        },
+        VariantKind::Tuple(_) => SynError::new_spanned(
+            ident,
+            "Tuple variants cannot be internally tagged, as they have no keys to sit next to the \
+             tag.",
+        )
+        .to_compile_error(),
+        VariantKind::Struct(fields) => {
+            let build = build_fields(ident, name, fields);
+
+            quote! {
+                {
+                    let ctx = &ctx.nested(#name);
+
+                    #build
+                }
+            }
+        },
+    }
+}
+
+/// Generates the expression parsing a value held by a variant, reporting
+/// errors within the section of the variant.
+fn parse_value(ty: &Type, name: &str, value: TokenStream) -> TokenStream {
+    quote! {
+        <#ty as ::cruct::FromConfigValue>::from_config_value_with(#value, ctx)
+            .map_err(|e| e.in_section(#name))?
+    }
+}
+
+/// Generates the expression building a variant holding named fields from
+/// the keys held in `map`, reporting errors within the section of the
+/// variant.
+fn build_fields(ident: &Ident, name: &str, fields: &[StructField]) -> TokenStream {
+    let field_inits = fields
+        .iter()
+        .map(|field| {
+            let default_params = FieldParams::default();
+            let params_ref = field
+                .params
+                .as_ref()
+                .unwrap_or(&default_params);
+
+            generate_field_initialization(params_ref, &field.ident, field.config_key(), &field.ty)
+        });
+
+    let flatten_check = generate_flatten_check(fields);
+
+    quote! {
+        {
+            #[allow(unused_variables)]
+            let map = &mut map;
+
+            #[allow(clippy::redundant_closure_call)]
+            let variant = (|| -> Result<Self, ParserError> {
+                #flatten_check
+                Ok(Self::#ident { #(#field_inits),* })
+            })();
+
+            variant.map_err(|e| e.in_section(#name))
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Error as SynError, Ident, Type, TypePath};
+use syn::{Error as SynError, Expr, Ident, Type, TypePath, parse_quote};
 
-use crate::parse::FieldParams;
+use crate::generate::generate_validations;
+use crate::parse::{FieldParams, StructField};
 
 /// Generates initialization logic for a single configuration field.
 /// This includes support for overrides (CLI/env), config file lookup, and
@@ -19,15 +20,26 @@ pub fn generate_field_initialization(
         return e.to_compile_error();
     }
 
-    let override_chain = build_override_chain(field);
+    let override_chain = build_override_chain(field, field_type);
     let config_lookup = build_config_lookup(field, config_key);
     let is_scalar = is_scalar_type(field_type);
 
+    // Struct defaults are moved out of the `defaults` built once per struct.
+    let struct_default: Option<Expr> = field
+        .struct_default
+        .then(|| parse_quote! { defaults.#field_ident });
+
     // TODO: clean this mess
 
-    let parse_logic = if field.optional {
+    let parse_logic = if field.flatten {
+        parse_flattened(field_type, config_key)
+    } else if field.optional {
         parse_optional(field_type, config_key, &override_chain, &config_lookup)
-    } else if let Some(default_val) = &field.default {
+    } else if let Some(default_val) = field
+        .default
+        .as_ref()
+        .or(struct_default.as_ref())
+    {
         parse_with_default(field_type, config_key, default_val, &override_chain, &config_lookup)
     } else if is_scalar {
         parse_scalar(field_type, config_key, &override_chain, &config_lookup)
@@ -35,6 +47,8 @@ pub fn generate_field_initialization(
         parse_nested(field_type, config_key, &override_chain, &config_lookup)
     };
 
+    let parse_logic = generate_validations(field, config_key, field_type, parse_logic);
+
     quote! { #field_ident: #parse_logic }
 }
 
@@ -78,36 +92,136 @@ fn is_scalar_type(ty: &Type) -> bool {
 
 /// Builds the override resolution chain for a field, combining CLI and ENV
 /// logic.
-fn build_override_chain(field: &FieldParams) -> TokenStream {
+///
+/// Overrides are resolved through the `ctx` load context in scope, so the
+/// arguments and environment can be supplied explicitly. Overrides given as a
+/// single string are converted with `FromConfigValue::override_value`, so a
+/// `Vec` can be set from a single value.
+fn build_override_chain(field: &FieldParams, ty: &Type) -> TokenStream {
     let cli = if let Some(flag) = &field.arg_override {
-        quote! {
-            std::env::args().skip(1)
-                .find_map(|arg| {
-                    let prefix = concat!("--", #flag, "=");
-                    arg.strip_prefix(prefix)
-                        .map(|v| ::cruct::parser::ConfigValue::Value(v.to_string()))
-                })
-        }
+        quote! { ctx.arg(#flag) }
     } else {
         quote! { None }
     };
 
     let env = if let Some(var) = &field.env_override {
         quote! {
-            std::env::var(#var)
-                .ok()
+            ctx.var(#var)
                 .map(|s| ::cruct::parser::ConfigValue::Value(s))
         }
     } else {
         quote! { None }
     };
 
-    quote! { #cli.or_else(|| #env) }
+    quote! {
+        #cli.or_else(|| #env)
+            .map(|value| match value {
+                ::cruct::parser::ConfigValue::Value(s) => {
+                    <#ty as ::cruct::FromConfigValue>::override_value(s)
+                },
+                value => value,
+            })
+    }
 }
 
 /// Builds the expression used to look up a field in the configuration map.
 /// Supports case-insensitive lookup if configured.
+///
+/// The key is looked up first, then each alias in order. If the field is
+/// deprecated, a warning is recorded in `ctx` when a deprecated key is found.
+/// A `ConflictingKey` warning is recorded for each other key of the field that
+/// is also set, as its value is ignored.
 fn build_config_lookup(field: &FieldParams, key: &str) -> TokenStream {
+    if field
+        .aliases
+        .is_empty()
+        && field
+            .deprecated
+            .is_none()
+    {
+        return lookup_key(field, key);
+    }
+
+    let keys = std::iter::once(key)
+        .chain(
+            field
+                .aliases
+                .iter()
+                .map(String::as_str),
+        )
+        .collect::<Vec<_>>();
+
+    let lookups = keys
+        .iter()
+        .map(|key| {
+            let lookup = lookup_key(field, key);
+            quote! { #lookup.map(|value| (#key, value)) }
+        });
+
+    // The aliases are deprecated if there are any, the key itself otherwise.
+    let deprecated_keys = match field
+        .aliases
+        .is_empty()
+    {
+        true => vec![key],
+        false => field
+            .aliases
+            .iter()
+            .map(String::as_str)
+            .collect(),
+    };
+
+    let warn = field
+        .deprecated
+        .as_ref()
+        .map(|message| {
+            quote! {
+                if let Some((found, _)) = &found {
+                    if [#(#deprecated_keys),*].contains(found) {
+                        ctx.warn(::cruct::ConfigWarning::DeprecatedKey {
+                            key: ctx.path_of(found),
+                            message: #message.to_string(),
+                        });
+                    }
+                }
+            }
+        });
+
+    let contains = match field.insensitive {
+        true => quote! {
+            map.keys()
+               .any(|k| k.eq_ignore_ascii_case(other) && !k.eq_ignore_ascii_case(found))
+        },
+        false => quote! { map.contains_key(other) },
+    };
+
+    let conflicts = (keys.len() > 1).then(|| {
+        quote! {
+            if let Some((found, _)) = &found {
+                for other in [#(#keys),*] {
+                    if other != *found && #contains {
+                        ctx.warn(::cruct::ConfigWarning::ConflictingKey {
+                            key: ctx.path_of(other),
+                            used: ctx.path_of(found),
+                        });
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        {
+            let found = None #(.or_else(|| #lookups))*;
+            #warn
+            #conflicts
+            found.map(|(_, value)| value)
+        }
+    }
+}
+
+/// Builds the expression looking up a single key in the configuration map.
+fn lookup_key(field: &FieldParams, key: &str) -> TokenStream {
     if field.insensitive {
         quote! {
             map.iter()
@@ -124,24 +238,19 @@ fn build_config_lookup(field: &FieldParams, key: &str) -> TokenStream {
 fn parse_with_default(
     ty: &Type,
     key: &str,
-    default_val: &syn::Expr,
+    default_val: &Expr,
     override_chain: &TokenStream,
     config_lookup: &TokenStream,
 ) -> TokenStream {
+    let convert = convert_value(ty, key, quote! { stringify!(#ty).into() });
+
     quote! {
         {
             let maybe = #override_chain.or_else(|| #config_lookup);
             if let Some(val) = maybe {
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&val)
-                    .map_err(|e| ::cruct::parser::ParserError::TypeMismatch {
-                        field: #key.to_string(),
-                        expected: stringify!(#ty).into(),
-                        found: val.to_string()
-                    })?
+                #convert
             } else {
-                let sec = ::cruct::ConfigValue::Section(map.clone());
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&sec)
-                    .unwrap_or(#default_val)
+                #default_val
             }
         }
     }
@@ -157,24 +266,47 @@ fn parse_optional(
     override_chain: &TokenStream,
     config_lookup: &TokenStream,
 ) -> TokenStream {
+    let convert = convert_value(ty, key, quote! { ::std::any::type_name::<#ty>().to_string() });
+
     quote! {
         {
             let maybe = #override_chain.or_else(|| #config_lookup);
             if maybe.is_none() {
                 None
             } else {
-                let maybe = maybe.unwrap();
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&maybe)
-                    .map_err(|e| ::cruct::parser::ParserError::TypeMismatch {
-                        field: #key.to_string(),
-                        expected: ::std::any::type_name::<#ty>().to_string(),
-                        found: maybe.to_string()
-                    })?
+                let val = maybe.unwrap();
+                #convert
             }
         }
     }
 }
 
+/// Generates the conversion of the value held in `val` into the field type.
+///
+/// Type mismatches of types without fields, such as scalars, are reported as
+/// a TypeMismatch of the field. Other errors, such as the ones of nested
+/// structs or unknown enum variants, are reported within the section of the
+/// field, so that their cause is kept.
+///
+/// * `expected`: The expression describing the expected type.
+fn convert_value(ty: &Type, key: &str, expected: TokenStream) -> TokenStream {
+    quote! {
+        <#ty as ::cruct::FromConfigValue>::from_config_value_with(&val, &ctx.nested(#key))
+            .map_err(|e| match e {
+                ::cruct::parser::ParserError::TypeMismatch { .. }
+                    if <#ty as ::cruct::FromConfigValue>::field_metadata().is_empty() =>
+                {
+                    ::cruct::parser::ParserError::TypeMismatch {
+                        field: #key.to_string(),
+                        expected: #expected,
+                        found: val.to_string()
+                    }
+                },
+                e => e.in_section(#key),
+            })?
+    }
+}
+
 /// Generates parsing logic for scalar fields without a default value.
 /// Returns a MissingField or TypeMismatch error if necessary.
 fn parse_scalar(
@@ -187,7 +319,7 @@ fn parse_scalar(
         {
             let maybe = #override_chain.or_else(|| #config_lookup);
             if let Some(val) = maybe {
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&val)
+                <#ty as ::cruct::FromConfigValue>::from_config_value_with(&val, &ctx.nested(#key))
                     .map_err(|_| ::cruct::parser::ParserError::TypeMismatch {
                         field: #key.to_string(),
                         expected: stringify!(#ty).into(),
@@ -203,7 +335,12 @@ fn parse_scalar(
 }
 
 /// Generates parsing logic for nested structs without a default value.
-/// If the key is not found, attempts to use the entire config map as a section.
+///
+/// If the key is not found, structs are read from an empty section, so that
+/// the section can be omitted when every field of the struct has a default.
+/// Returns a MissingSection error if a field or section of the struct is
+/// missing, any other error within the section of the field, or a
+/// MissingField error for types that are not read from a section.
 fn parse_nested(
     ty: &Type,
     key: &str,
@@ -214,26 +351,131 @@ fn parse_nested(
         {
             let maybe = #override_chain.or_else(|| #config_lookup);
             if let Some(val) = maybe {
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&val)
-                    .map_err(|e| ::cruct::parser::ParserError::NestedError {
-                        section: #key.to_string(), source: Box::new(e)
-                    })?
+                <#ty as ::cruct::FromConfigValue>::from_config_value_with(&val, &ctx.nested(#key))
+                    .map_err(|e| e.in_section(#key))?
+            } else if <#ty as ::cruct::FromConfigValue>::field_metadata().is_empty() {
+                return Err(::cruct::parser::ParserError::MissingField(
+                    #key.to_string(),
+                ));
             } else {
-                let sec = ::cruct::ConfigValue::Section(map.clone());
-                <#ty as ::cruct::FromConfigValue>::from_config_value(&sec)
-                    .map_err(|e| ::cruct::parser::ParserError::NestedError {
-                        section: #key.to_string(), source: Box::new(e)
+                let sec = ::cruct::ConfigValue::Section(::std::collections::HashMap::new());
+                <#ty as ::cruct::FromConfigValue>::from_config_value_with(&sec, &ctx.nested(#key))
+                    .map_err(|e| match e {
+                        ::cruct::parser::ParserError::MissingField(_)
+                        | ::cruct::parser::ParserError::MissingSection(_) => {
+                            ::cruct::parser::ParserError::MissingSection(#key.to_string())
+                        },
+                        e => e.in_section(#key),
                     })?
             }
         }
     }
 }
 
+/// Generates parsing logic for flattened fields, whose struct reads its keys
+/// from the parent section, through the same context.
+fn parse_flattened(ty: &Type, key: &str) -> TokenStream {
+    quote! {
+        {
+            <#ty as ::cruct::FromConfigValue>::from_config_section(map, ctx)
+                .map_err(|e| e.in_section(#key))?
+        }
+    }
+}
+
+/// Generates a check that the keys of every flattened field, including the
+/// ones flattened into it, are not used by another field of the struct.
+///
+/// Returns a `KeyCollision` error naming the flattened field and the key.
+pub fn generate_flatten_check(fields: &[StructField]) -> TokenStream {
+    let (flattened, own): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .partition(|field| field.is_flattened());
+
+    if flattened.is_empty() {
+        return TokenStream::new();
+    }
+
+    let own_keys = own
+        .iter()
+        .flat_map(|field| {
+            let aliases = field
+                .params
+                .iter()
+                .flat_map(|params| {
+                    params
+                        .aliases
+                        .iter()
+                        .map(String::as_str)
+                });
+
+            std::iter::once(field.config_key()).chain(aliases)
+        });
+
+    let checks = flattened
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            let key = field.config_key();
+
+            quote! {
+                for nested in <#ty as ::cruct::FromConfigValue>::field_metadata() {
+                    if keys.contains(&nested.key) {
+                        return Err(::cruct::parser::ParserError::KeyCollision {
+                            field: ctx.path_of(#key),
+                            key: ctx.path_of(nested.key),
+                        });
+                    }
+
+                    keys.push(nested.key);
+                }
+            }
+        });
+
+    quote! {
+        {
+            let mut keys: Vec<&str> = vec![#(#own_keys),*];
+            #(#checks)*
+        }
+    }
+}
+
 /// Performs compile-time checks on field parameters to ensure they are valid.
 ///
 /// * `field`: The field parameters to check.
 /// * `field_ident`: The identifier of the field, used for error reporting.
 fn compile_check(field: &FieldParams, field_ident: &Ident) -> Result<(), syn::Error> {
+    if field.flatten
+        && (field.optional
+            || field
+                .default
+                .is_some()
+            || field
+                .name
+                .is_some()
+            || !field
+                .aliases
+                .is_empty()
+            || field
+                .deprecated
+                .is_some()
+            || field
+                .env_override
+                .is_some()
+            || field
+                .arg_override
+                .is_some())
+    {
+        return Err(SynError::new_spanned(
+            field_ident,
+            format!(
+                "Field `{}` is flattened, so it has no key and cannot have a name, alias, \
+                 override or default value, nor be optional.",
+                field_ident
+            ),
+        ));
+    }
+
     if field.optional
         && field
             .default
@@ -0,0 +1,31 @@
+use syn::{GenericParam, Generics, parse_quote};
+
+/// Returns the generics of an annotated item, requiring every type parameter
+/// to implement `FromConfigValue`, so that fields of those types can be
+/// loaded.
+pub fn with_config_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+
+    let bounds = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => Some(
+                param
+                    .ident
+                    .clone(),
+            ),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let where_clause = generics.make_where_clause();
+
+    for ident in bounds {
+        where_clause
+            .predicates
+            .push(parse_quote! { #ident: ::cruct::FromConfigValue });
+    }
+
+    generics
+}
@@ -1,9 +1,15 @@
-use cruct_shared::FileFormat;
+use cruct_shared::{FileFormat, MergeStrategy};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, LitStr};
+use syn::{Generics, Ident, LitStr, parse_quote};
 
-use crate::generate::generate_field_initialization;
+use crate::generate::{
+    generate_cli_flags,
+    generate_field_initialization,
+    generate_field_metadata,
+    generate_flatten_check,
+    with_config_bounds,
+};
 use crate::parse::{FieldParams, MacroParams, StructField};
 
 /// Generate the implementation block for a struct annotated with `#[cruct]`.
@@ -13,26 +19,42 @@ use crate::parse::{FieldParams, MacroParams, StructField};
 ///     2. A `load_from` method that deserializes a `ConfigValue::Section` into
 ///        your struct.
 ///     3. An implementation of `FromConfigValue` so your struct can be used
-///        anywhere a nested struct is expected (for flat-nesting support).
+///        anywhere a nested struct is expected, either in a section of its own
+///        or flattened into its parent.
 pub fn generate_impl_block(
     struct_name: &Ident,
+    generics: &Generics,
     params: &MacroParams,
     fields: &[StructField],
 ) -> TokenStream {
     let loader_name = Ident::new(&format!("{}Loader", struct_name), struct_name.span());
 
+    let mut generics = with_config_bounds(generics);
+
+    // Fields set by `#[cruct(default)]` are taken from a single `Default`
+    // value of the struct, built before its fields.
+    let defaults = fields
+        .iter()
+        .any(StructField::has_struct_default)
+        .then(|| {
+            let (_, ty_generics, _) = generics.split_for_impl();
+            let bound = parse_quote! { #struct_name #ty_generics: ::std::default::Default };
+
+            generics
+                .make_where_clause()
+                .predicates
+                .push(bound);
+
+            quote! { let defaults = <Self as ::std::default::Default>::default(); }
+        });
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     let field_inits = fields
         .iter()
         .map(|field| {
             let field_ident = &field.ident;
-            let config_key = field
-                .params
-                .as_ref()
-                .and_then(|p| {
-                    p.name
-                        .as_ref()
-                })
-                .unwrap_or(&field.name);
+            let config_key = field.config_key();
 
             let default_params = FieldParams::default();
             let params_ref = field
@@ -42,6 +64,100 @@ pub fn generate_impl_block(
             generate_field_initialization(params_ref, field_ident, config_key, &field.ty)
         });
 
+    let flatten_check = generate_flatten_check(fields);
+
+    // The keys read by the fields, removed from the section once they are
+    // built, along with whether they are matched case-insensitively.
+    let known_keys = fields
+        .iter()
+        .filter(|field| !field.is_flattened())
+        .flat_map(|field| {
+            let params = field
+                .params
+                .as_ref();
+            let insensitive = params.is_some_and(|p| p.insensitive);
+
+            std::iter::once(field.config_key())
+                .chain(
+                    params
+                        .into_iter()
+                        .flat_map(|p| {
+                            p.aliases
+                                .iter()
+                                .map(String::as_str)
+                        }),
+                )
+                .map(move |key| quote! { (#key, #insensitive) })
+        })
+        .collect::<Vec<_>>();
+    let known_count = known_keys.len();
+
+    let validate = params
+        .validate
+        .as_ref()
+        .map(|validate| {
+            quote! {
+                if let Err(e) = #validate(&value) {
+                    return Err(::cruct::parser::ParserError::InvalidStruct {
+                        path: ctx.path().to_string(),
+                        message: e.to_string(),
+                    });
+                }
+            }
+        });
+
+    let deny_unknown_fields = params.deny_unknown_fields;
+    let flattened_types = fields
+        .iter()
+        .filter(|field| field.is_flattened())
+        .map(|field| &field.ty);
+
+    let merge_strategies = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            let config_key = field.config_key();
+
+            if field.is_flattened() {
+                return quote! {
+                    strategies.extend(<#ty as ::cruct::FromConfigValue>::merge_strategies());
+                };
+            }
+
+            // Aliases hold the same value as the key, so they are merged with
+            // the same strategies.
+            let aliases = field
+                .params
+                .iter()
+                .flat_map(|p| {
+                    p.aliases
+                        .iter()
+                        .map(String::as_str)
+                });
+            let keys = std::iter::once(config_key)
+                .chain(aliases)
+                .collect::<Vec<_>>();
+
+            let own = field
+                .params
+                .as_ref()
+                .and_then(|p| {
+                    p.merge
+                        .as_ref()
+                })
+                .map(|strategy| {
+                    let strategy = merge_strategy_tokens(strategy);
+                    quote! { #(strategies.push((#keys.to_string(), #strategy));)* }
+                });
+
+            quote! {
+                #own
+                for (path, strategy) in <#ty as ::cruct::FromConfigValue>::merge_strategies() {
+                    #(strategies.push((format!("{}.{}", #keys, path), strategy.clone()));)*
+                }
+            }
+        });
+
     let config_adds = params
         .configs
         .iter()
@@ -59,37 +175,149 @@ pub fn generate_impl_block(
 
                 None => quote! { None },
             };
+            let profiles = cfg
+                .profiles
+                .then(|| quote! { .with_profiles() });
             quote! {
                 self.builder = self.builder.add_source(
-                    ::cruct::ConfigFileSource::new(#path_lit, #format_ts)
+                    ::cruct::ConfigFileSource::new(#path_lit, #format_ts)#profiles
                 );
             }
         });
 
+    let cli_flags = generate_cli_flags(fields);
+    let field_metadata = generate_field_metadata(fields);
+
+    let interpolate = params.interpolate;
+
+    let profile_env = params
+        .profile_env
+        .as_ref()
+        .map(|var| quote! { .with_profile_env(#var) });
+
     quote! {
         /// Builder type for loading a `<#struct_name>` from CLI, ENV, and config files.
-        pub struct #loader_name {
+        pub struct #loader_name #impl_generics #where_clause {
             builder: ::cruct::ConfigBuilder,
+            marker: ::std::marker::PhantomData<fn() -> #struct_name #ty_generics>,
         }
 
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
             /// Create a new loader for this struct.
-            pub fn loader() -> #loader_name {
-                #loader_name {
-                    builder: ::cruct::ConfigBuilder::new()
+            pub fn loader() -> #loader_name #ty_generics {
+                let mut builder = ::cruct::ConfigBuilder::new()
+                    .with_interpolation(#interpolate)
+                    .with_cli_definition(Self::cli_definition())
+                    #profile_env;
+
+                for (path, strategy) in <Self as ::cruct::FromConfigValue>::merge_strategies() {
+                    builder = builder.with_merge_strategy(path, strategy);
                 }
+
+                #loader_name { builder, marker: ::std::marker::PhantomData }
+            }
+
+            /// Returns the metadata of the fields of this struct, such as their
+            /// keys, types, defaults, overrides and descriptions.
+            pub fn field_metadata() -> Vec<::cruct::FieldMetadata> {
+                <Self as ::cruct::FromConfigValue>::field_metadata()
+            }
+
+            /// Returns a JSON Schema (draft 2020-12) describing the configuration
+            /// files accepted by this struct, for editors to validate and complete
+            /// them with.
+            pub fn json_schema() -> String {
+                ::cruct::json_schema(stringify!(#struct_name), &Self::field_metadata())
+            }
+
+            /// Renders an example configuration file in the given format, listing
+            /// every key along with its description and default value.
+            pub fn example_config(format: ::cruct::FileFormat) -> String {
+                ::cruct::example_config(&Self::field_metadata(), format)
+            }
+
+            /// Renders a Markdown table documenting every key of the configuration,
+            /// along with its type, default value, overrides and description.
+            pub fn markdown_reference() -> String {
+                ::cruct::markdown_reference(&Self::field_metadata())
+            }
+
+            /// Returns the command-line interface generated for this struct,
+            /// which renders the `--help` text. Its flags are the fields with an
+            /// `arg_override`, and its keys the ones of every field.
+            pub fn cli_definition() -> ::cruct::CliDefinition {
+                ::cruct::CliDefinition::new(
+                    env!("CARGO_PKG_NAME"),
+                    <Self as ::cruct::FromConfigValue>::cli_flags(),
+                )
+                .with_keys(&Self::field_metadata())
             }
         }
 
-        impl #loader_name {
+        impl #impl_generics #loader_name #ty_generics #where_clause {
             /// Add a CLI source with the given priority.
+            ///
+            /// The flags of `cli_definition()` set the key they are defined for,
+            /// as do flags named after a key, such as `--database.port`. Other
+            /// flags are skipped and returned as `UnknownFlag` warnings by
+            /// `load_with_warnings`, unless `with_strict_cli` is set.
             pub fn with_cli(mut self, priority: u8) -> Self {
-                self.builder = self.builder.add_source(
-                    ::cruct::CliSource::new(priority)
-                );
+                self.builder = self.builder.with_cli(priority);
+                self
+            }
+
+            /// Only accept the flags listed by `cli_definition()`, the fields with an
+            /// `arg_override`, on the command line:
+            /// `--help` returns a `HelpRequested` error holding the help text,
+            /// and unknown flags, malformed flags and positional arguments are
+            /// reported as errors.
+            pub fn with_strict_cli(mut self) -> Self {
+                self.builder = self.builder.with_strict_cli();
                 self
             }
 
+            /// Use the given command-line arguments, without the program name,
+            /// instead of the ones the process was started with.
+            pub fn with_args<I, S>(mut self, args: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                self.builder = self.builder.with_args(args);
+                self
+            }
+
+            /// Look up environment variables through the given function instead
+            /// of the process environment.
+            pub fn with_env_provider<F>(mut self, provider: F) -> Self
+            where
+                F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+            {
+                self.builder = self.builder.with_env_provider(provider);
+                self
+            }
+
+            /// Add a custom configuration source, such as a
+            /// `MergePatchSource` or a `JsonPatchSource`.
+            pub fn with_source<S>(mut self, source: S) -> Self
+            where
+                S: ::cruct::ConfigSource + Send + Sync + 'static,
+            {
+                self.builder = self.builder.add_source(source);
+                self
+            }
+
+            /// Select the profile to load, such as `dev` or `prod`.
+            pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+                self.builder = self.builder.with_profile(profile);
+                self
+            }
+
+            /// Returns the profile that will be loaded, if any.
+            pub fn active_profile(&self) -> Option<String> {
+                self.builder.active_profile()
+            }
+
             /// Add all `load_config(...)` sources specified on the struct.
             pub fn with_config(mut self) -> Self {
                 #(#config_adds)*
@@ -101,22 +329,53 @@ pub fn generate_impl_block(
             /// # Errors
             /// Returns a `ParserError` if any required field is missing, or
             /// if any parsing or nested error occurs.
-            pub fn load(self) -> Result<#struct_name, ::cruct::ParserError> {
-                let cfg_val = self.builder.load()?;
-                #struct_name::load_from(&cfg_val)
+            pub fn load(self) -> Result<#struct_name #ty_generics, ::cruct::ParserError> {
+                self.load_with_warnings()
+                    .map(|(value, _)| value)
             }
-        }
 
-        impl #struct_name {
-            /// Deserialize from a `ConfigValue` (must be a `Section`).
+            /// Load like `load`, also returning the warnings raised while loading,
+            /// such as deprecated keys being set.
             ///
-            /// This is called internally by `load`, or when flattening nested structs.
+            /// # Errors
+            /// Returns a `ParserError` if any required field is missing, or
+            /// if any parsing or nested error occurs.
+            pub fn load_with_warnings(
+                self
+            ) -> Result<(#struct_name #ty_generics, Vec<::cruct::ConfigWarning>), ::cruct::ParserError> {
+                let ctx = self.builder.context();
+                let cfg_val = self.builder.load_with(&ctx)?;
+                let value = #struct_name::load_from_with(&cfg_val, &ctx)?;
+                Ok((value, ctx.take_warnings()))
+            }
+        }
+
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Deserialize from a `ConfigValue` (must be a `Section`), resolving
+            /// field overrides through the process environment and arguments.
             ///
             /// # Errors
             /// - `TypeMismatch` if the top-level value is not a section.
             /// - Nested errors for each field via `NestedError`.
             pub fn load_from(
                 config: &::cruct::ConfigValue
+            ) -> Result<Self, ::cruct::ParserError> {
+                #struct_name::load_from_with(config, &::cruct::LoadContext::from_process())
+            }
+
+            /// Deserialize from a `ConfigValue` (must be a `Section`), resolving
+            /// field overrides through the given context.
+            ///
+            /// Keys that no field reads are recorded as `UnknownKey` warnings in
+            /// the context, or reported as an `UnknownKeys` error if the struct
+            /// or a struct it flattens denies unknown fields.
+            ///
+            /// # Errors
+            /// - `TypeMismatch` if the top-level value is not a section.
+            /// - Nested errors for each field via `NestedError`.
+            pub fn load_from_with(
+                config: &::cruct::ConfigValue,
+                ctx: &::cruct::LoadContext,
             ) -> Result<Self, ::cruct::ParserError> {
                 use ::cruct::{ConfigValue, ParserError};
 
@@ -132,20 +391,104 @@ pub fn generate_impl_block(
                     }
                 };
 
-                Ok(Self {
-                    #(#field_inits),*
-                })
+                let value =
+                    <Self as ::cruct::FromConfigValue>::from_config_section(&mut map, ctx)?;
+
+                if !map.is_empty() {
+                    let known = Self::field_metadata();
+                    let known = known
+                        .iter()
+                        .map(|field| field.key)
+                        .collect::<Vec<_>>();
+
+                    ctx.unknown_keys(map.keys(), &known);
+                }
+
+                if <Self as ::cruct::FromConfigValue>::denies_unknown_fields() {
+                    let unknown = ctx.take_unknown_keys();
+                    if !unknown.is_empty() {
+                        return Err(ParserError::UnknownKeys(unknown));
+                    }
+                }
+
+                Ok(value)
             }
         }
 
         /// Allow this struct itself to be treated as a nested config value.
-        /// This supports flat-nested loading when a struct appears inside another.
-        impl ::cruct::FromConfigValue for #struct_name {
+        /// This supports nested and flattened loading when a struct appears inside another.
+        impl #impl_generics ::cruct::FromConfigValue for #struct_name #ty_generics #where_clause {
             fn from_config_value(
                 value: &::cruct::ConfigValue
             ) -> Result<Self, ::cruct::ParserError> {
-                #struct_name::load_from(value)
+                #struct_name::load_from_with(value, &::cruct::LoadContext::from_process())
+            }
+
+            fn from_config_value_with(
+                value: &::cruct::ConfigValue,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                #struct_name::load_from_with(value, ctx)
+            }
+
+            fn from_config_section(
+                map: &mut ::std::collections::HashMap<String, ::cruct::ConfigValue>,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                #flatten_check
+
+                #defaults
+
+                let value = Self {
+                    #(#field_inits),*
+                };
+
+                #validate
+
+                let known: [(&str, bool); #known_count] = [#(#known_keys),*];
+                map.retain(|key, _| {
+                    !known
+                        .iter()
+                        .any(|(k, insensitive)| {
+                            if *insensitive { key.eq_ignore_ascii_case(k) } else { key == k }
+                        })
+                });
+
+                Ok(value)
+            }
+
+            fn merge_strategies() -> Vec<(String, ::cruct::MergeStrategy)> {
+                let mut strategies = Vec::new();
+                #(#merge_strategies)*
+                strategies
+            }
+
+            fn denies_unknown_fields() -> bool {
+                #deny_unknown_fields
+                    #(|| <#flattened_types as ::cruct::FromConfigValue>::denies_unknown_fields())*
+            }
+
+            fn cli_flags() -> Vec<::cruct::CliFlag> {
+                let mut flags = Vec::new();
+                #cli_flags
+                flags
+            }
+
+            fn field_metadata() -> Vec<::cruct::FieldMetadata> {
+                #field_metadata
             }
         }
     }
 }
+
+/// Converts a merge strategy into the tokens that construct it at runtime.
+pub fn merge_strategy_tokens(strategy: &MergeStrategy) -> TokenStream {
+    match strategy {
+        MergeStrategy::Replace => quote! { ::cruct::MergeStrategy::Replace },
+        MergeStrategy::Append => quote! { ::cruct::MergeStrategy::Append },
+        MergeStrategy::Prepend => quote! { ::cruct::MergeStrategy::Prepend },
+        MergeStrategy::ByKey(key) => {
+            quote! { ::cruct::MergeStrategy::ByKey(#key.to_string()) }
+        },
+    }
+}
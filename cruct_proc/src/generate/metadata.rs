@@ -0,0 +1,235 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::{ToTokens, quote};
+use syn::{Expr, ExprCall, ExprLit, ExprMethodCall, ExprUnary, Lit, Type, TypePath, UnOp};
+
+use crate::generate::impl_block::merge_strategy_tokens;
+use crate::parse::StructField;
+
+/// Generates the body of `FromConfigValue::field_metadata` for a struct,
+/// describing each of its fields.
+///
+/// Flattened fields are described by the fields of their struct instead.
+pub fn generate_field_metadata(fields: &[StructField]) -> TokenStream {
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            let name = &field.name;
+            let key = field.config_key();
+            let ty_name = type_name(ty);
+
+            if field.is_flattened() {
+                return quote! { <#ty as ::cruct::FromConfigValue>::field_metadata() };
+            }
+
+            let params = field
+                .params
+                .as_ref();
+
+            let default = option_tokens(field_default_tokens(field));
+            let env = option_tokens(params.and_then(|p| {
+                p.env_override
+                    .as_ref()
+            }));
+            let arg = option_tokens(params.and_then(|p| {
+                p.arg_override
+                    .as_ref()
+            }));
+            let description = option_tokens(
+                field
+                    .description
+                    .as_ref(),
+            );
+            let merge = option_tokens(
+                params
+                    .and_then(|p| {
+                        p.merge
+                            .as_ref()
+                    })
+                    .map(merge_strategy_tokens),
+            );
+
+            let optional = params.is_some_and(|p| p.optional) || is_option(ty);
+
+            quote! {
+                vec![::cruct::FieldMetadata {
+                    name: #name,
+                    key: #key,
+                    ty: #ty_name,
+                    default: #default,
+                    env: #env,
+                    arg: #arg,
+                    description: #description,
+                    optional: #optional,
+                    merge: #merge,
+                    fields: <#ty as ::cruct::FromConfigValue>::field_metadata(),
+                    variants: <#ty as ::cruct::FromConfigValue>::variants(),
+                    type_metadata: <#ty as ::cruct::FromConfigValue>::type_metadata(),
+                }]
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let count = fields.len();
+
+    quote! {
+        let fields: [Vec<::cruct::FieldMetadata>; #count] = [#(#fields),*];
+        fields.concat()
+    }
+}
+
+/// Generates the `FieldMetadata` describing a value held by a tuple struct
+/// or an enum variant, keyed by its index or the name of the variant.
+pub fn value_metadata(key: &str, ty: &Type) -> TokenStream {
+    let ty_name = type_name(ty);
+    let optional = is_option(ty);
+
+    quote! {
+        ::cruct::FieldMetadata {
+            name: #key,
+            key: #key,
+            ty: #ty_name,
+            default: None,
+            env: None,
+            arg: None,
+            description: None,
+            optional: #optional,
+            merge: None,
+            fields: <#ty as ::cruct::FromConfigValue>::field_metadata(),
+            variants: <#ty as ::cruct::FromConfigValue>::variants(),
+            type_metadata: <#ty as ::cruct::FromConfigValue>::type_metadata(),
+        }
+    }
+}
+
+/// Renders a type as written, without whitespace, such as `Vec<String>`.
+pub fn type_name(ty: &Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(' ', "")
+}
+
+/// Whether the type is an `Option`.
+pub fn is_option(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Path(TypePath { path, .. })
+            if path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option")
+    )
+}
+
+/// Converts an optional value into the tokens of an `Option` holding its
+/// tokens, such as `Some("port")` for a string.
+pub fn option_tokens<T: ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+/// Generates the `DefaultValue` describing the default of a field, either
+/// its own or the one taken from the `Default` implementation of its struct.
+pub fn field_default_tokens(field: &StructField) -> Option<TokenStream> {
+    if field.has_struct_default() {
+        let text = format!("Self::default().{}", field.ident);
+        return Some(quote! { ::cruct::DefaultValue::Expr(#text) });
+    }
+
+    field
+        .params
+        .as_ref()
+        .and_then(|p| {
+            p.default
+                .as_ref()
+        })
+        .map(default_value_tokens)
+}
+
+/// Generates the `DefaultValue` describing a default value expression.
+///
+/// Literals are described by their value, as are string literals converted
+/// with `to_string`, `to_owned` or `into`. The call made by `default` alone is
+/// described as `Default::default()`. Other expressions are described as
+/// written.
+pub fn default_value_tokens(expr: &Expr) -> TokenStream {
+    match expr {
+        Expr::Call(ExprCall { func, args, .. })
+            if args.is_empty()
+                && func
+                    .to_token_stream()
+                    .to_string()
+                    == ":: core :: default :: Default :: default" =>
+        {
+            quote! { ::cruct::DefaultValue::Expr("Default::default()") }
+        },
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Bool(b) => quote! { ::cruct::DefaultValue::Bool(#b) },
+            Lit::Int(int) => match int.base10_parse::<i128>() {
+                Ok(i) => integer_tokens(i),
+                Err(_) => expr_tokens(expr),
+            },
+            Lit::Float(float) => match float.base10_parse::<f64>() {
+                Ok(x) => float_tokens(x),
+                Err(_) => expr_tokens(expr),
+            },
+            Lit::Str(s) => {
+                let s = s.value();
+                quote! { ::cruct::DefaultValue::Str(#s) }
+            },
+            Lit::Char(c) => {
+                let s = c
+                    .value()
+                    .to_string();
+                quote! { ::cruct::DefaultValue::Str(#s) }
+            },
+            _ => expr_tokens(expr),
+        },
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr: inner, .. }) => match &**inner {
+            Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) => match int.base10_parse::<i128>() {
+                Ok(i) => integer_tokens(-i),
+                Err(_) => expr_tokens(expr),
+            },
+            Expr::Lit(ExprLit { lit: Lit::Float(float), .. }) => {
+                match float.base10_parse::<f64>() {
+                    Ok(x) => float_tokens(-x),
+                    Err(_) => expr_tokens(expr),
+                }
+            },
+            _ => expr_tokens(expr),
+        },
+        Expr::MethodCall(ExprMethodCall { receiver, method, args, .. })
+            if args.is_empty()
+                && matches!(**receiver, Expr::Lit(ExprLit { lit: Lit::Str(_), .. }))
+                && ["to_string", "to_owned", "into"]
+                    .iter()
+                    .any(|name| method == name) =>
+        {
+            default_value_tokens(receiver)
+        },
+        expr => expr_tokens(expr),
+    }
+}
+
+/// Generates an integer `DefaultValue`.
+fn integer_tokens(i: i128) -> TokenStream {
+    let i = Literal::i128_unsuffixed(i);
+    quote! { ::cruct::DefaultValue::Integer(#i) }
+}
+
+/// Generates a floating point `DefaultValue`.
+fn float_tokens(x: f64) -> TokenStream {
+    let x = Literal::f64_unsuffixed(x);
+    quote! { ::cruct::DefaultValue::Float(#x) }
+}
+
+/// Generates a `DefaultValue` holding an expression as written.
+fn expr_tokens(expr: &Expr) -> TokenStream {
+    let text = expr
+        .to_token_stream()
+        .to_string()
+        .replace(" :: ", "::");
+    quote! { ::cruct::DefaultValue::Expr(#text) }
+}
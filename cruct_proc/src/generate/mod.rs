@@ -1,8 +1,20 @@
+pub mod cli;
+pub mod enum_block;
 pub mod fields;
+pub mod generics;
 pub mod impl_block;
+pub mod metadata;
+pub mod tuple_block;
+pub mod validation;
 
 #[cfg(test)]
 mod tests;
 
-pub use fields::generate_field_initialization;
+pub use cli::generate_cli_flags;
+pub use enum_block::generate_enum_block;
+pub use fields::{generate_field_initialization, generate_flatten_check};
+pub use generics::with_config_bounds;
 pub use impl_block::generate_impl_block;
+pub use metadata::generate_field_metadata;
+pub use tuple_block::generate_tuple_block;
+pub use validation::generate_validations;
@@ -10,8 +10,15 @@ fn default_value_generates_correct_initialization() {
         name: None,
         insensitive: false,
         default: Some(parse_str("42").unwrap()),
+        struct_default: false,
         arg_override: None,
         optional: false,
+        merge: None,
+        description: None,
+        aliases: Vec::new(),
+        deprecated: None,
+        flatten: false,
+        validations: Vec::new(),
     };
     let ident: Ident = parse_quote! { bar };
     let ty: Type = parse_quote! { u32 };
@@ -20,6 +27,19 @@ fn default_value_generates_correct_initialization() {
     assert!(tokens.contains("42"));
 }
 
+#[test]
+fn struct_default_is_taken_from_defaults() {
+    let params = FieldParams {
+        struct_default: true,
+        ..parse_str("").unwrap()
+    };
+    let ident: Ident = parse_quote! { bar };
+    let ty: Type = parse_quote! { u32 };
+    let tokens = generate_field_initialization(&params, &ident, "bar", &ty).to_string();
+
+    assert!(tokens.contains("defaults . bar"));
+}
+
 #[test]
 fn arg_override_generates_correct_lookup() {
     let params = FieldParams {
@@ -27,14 +47,61 @@ fn arg_override_generates_correct_lookup() {
         name: None,
         insensitive: false,
         default: None,
+        struct_default: false,
         arg_override: Some("foo".into()),
         optional: false,
+        merge: None,
+        description: None,
+        aliases: Vec::new(),
+        deprecated: None,
+        flatten: false,
+        validations: Vec::new(),
     };
     let ident: Ident = parse_quote! { foo };
     let ty: Type = parse_quote! { String };
     let tokens = generate_field_initialization(&params, &ident, "foo", &ty).to_string();
 
-    // Expect to see args().skip(1).find_map for "--flag=" prefix
-    assert!(tokens.contains("std :: env :: args () . skip (1) . find_map"));
-    assert!(tokens.contains("\"--\" , \"foo\" , \"=\""));
+    // Expect the flag to be looked up through the load context
+    assert!(tokens.contains("ctx . arg (\"foo\")"));
+}
+
+#[test]
+fn aliases_are_looked_up_after_the_key() {
+    let params = FieldParams {
+        aliases: vec!["port".into()],
+        deprecated: Some("use http_port instead".into()),
+        ..FieldParams::default()
+    };
+    let ident: Ident = parse_quote! { http_port };
+    let ty: Type = parse_quote! { u16 };
+    let tokens = generate_field_initialization(&params, &ident, "http_port", &ty).to_string();
+
+    assert!(tokens.contains("map . remove (\"http_port\")"));
+    assert!(tokens.contains("map . remove (\"port\")"));
+    assert!(tokens.contains("use http_port instead"));
+}
+
+#[test]
+fn flattened_fields_are_read_from_the_parent_section() {
+    let params = FieldParams { flatten: true, ..FieldParams::default() };
+    let ident: Ident = parse_quote! { server };
+    let ty: Type = parse_quote! { Server };
+    let tokens = generate_field_initialization(&params, &ident, "server", &ty).to_string();
+
+    assert!(tokens.contains("from_config_section (map , ctx)"));
+    assert!(!tokens.contains("map . remove (\"server\")"));
+}
+
+#[test]
+fn flattened_fields_cannot_have_a_key() {
+    let params = FieldParams {
+        flatten: true,
+        name: Some("srv".into()),
+        ..FieldParams::default()
+    };
+    let ident: Ident = parse_quote! { server };
+    let ty: Type = parse_quote! { Server };
+    let tokens = generate_field_initialization(&params, &ident, "srv", &ty).to_string();
+
+    assert!(tokens.contains("compile_error"));
 }
@@ -0,0 +1,140 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, Ident, Type};
+
+use crate::generate::metadata::{type_name, value_metadata};
+use crate::generate::with_config_bounds;
+
+/// Generate the implementation of `FromConfigValue` for a tuple struct
+/// annotated with `#[cruct]`.
+///
+/// Newtypes, holding a single value, are read as the value they wrap, such
+/// as `port = 8080` for `struct Port(u16)`, and forward the merge
+/// strategies, flags and metadata of the wrapped type. Other tuple structs
+/// are read from an array holding one item per field.
+pub fn generate_tuple_block(
+    struct_name: &Ident,
+    generics: &Generics,
+    types: &[Type],
+) -> TokenStream {
+    let generics = with_config_bounds(generics);
+
+    match types {
+        [ty] => generate_newtype(struct_name, &generics, ty),
+        types => generate_tuple(struct_name, &generics, types),
+    }
+}
+
+/// Generates the implementation for a newtype, delegating to the wrapped type.
+fn generate_newtype(struct_name: &Ident, generics: &Generics, ty: &Type) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty_name = type_name(ty);
+
+    quote! {
+        impl #impl_generics ::cruct::FromConfigValue for #struct_name #ty_generics #where_clause {
+            fn from_config_value(
+                value: &::cruct::ConfigValue
+            ) -> Result<Self, ::cruct::ParserError> {
+                <#ty as ::cruct::FromConfigValue>::from_config_value(value).map(Self)
+            }
+
+            fn from_config_value_with(
+                value: &::cruct::ConfigValue,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                <#ty as ::cruct::FromConfigValue>::from_config_value_with(value, ctx).map(Self)
+            }
+
+            fn from_config_section(
+                map: &mut ::std::collections::HashMap<String, ::cruct::ConfigValue>,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                <#ty as ::cruct::FromConfigValue>::from_config_section(map, ctx).map(Self)
+            }
+
+            fn override_value(value: String) -> ::cruct::ConfigValue {
+                <#ty as ::cruct::FromConfigValue>::override_value(value)
+            }
+
+            fn merge_strategies() -> Vec<(String, ::cruct::MergeStrategy)> {
+                <#ty as ::cruct::FromConfigValue>::merge_strategies()
+            }
+
+            fn denies_unknown_fields() -> bool {
+                <#ty as ::cruct::FromConfigValue>::denies_unknown_fields()
+            }
+
+            fn cli_flags() -> Vec<::cruct::CliFlag> {
+                <#ty as ::cruct::FromConfigValue>::cli_flags()
+            }
+
+            fn field_metadata() -> Vec<::cruct::FieldMetadata> {
+                <#ty as ::cruct::FromConfigValue>::field_metadata()
+            }
+
+            fn variants() -> Vec<&'static str> {
+                <#ty as ::cruct::FromConfigValue>::variants()
+            }
+
+            fn type_metadata() -> Option<::cruct::TypeMetadata> {
+                Some(::cruct::TypeMetadata::Newtype {
+                    ty: #ty_name,
+                    inner: <#ty as ::cruct::FromConfigValue>::type_metadata().map(Box::new),
+                })
+            }
+        }
+    }
+}
+
+/// Generates the implementation for a tuple struct, read from an array
+/// holding one item per field.
+fn generate_tuple(struct_name: &Ident, generics: &Generics, types: &[Type]) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = types.len();
+    let items = types
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| {
+            quote! {
+                <#ty as ::cruct::FromConfigValue>::from_config_value_with(&items[#i], ctx)
+                    .map_err(|e| ParserError::NestedError {
+                        section: format!("index {}", #i),
+                        source: Box::new(e),
+                    })?
+            }
+        });
+    let metadata = types
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| value_metadata(&i.to_string(), ty));
+
+    quote! {
+        impl #impl_generics ::cruct::FromConfigValue for #struct_name #ty_generics #where_clause {
+            fn from_config_value(
+                value: &::cruct::ConfigValue
+            ) -> Result<Self, ::cruct::ParserError> {
+                Self::from_config_value_with(value, &::cruct::LoadContext::from_process())
+            }
+
+            fn from_config_value_with(
+                value: &::cruct::ConfigValue,
+                ctx: &::cruct::LoadContext,
+            ) -> Result<Self, ::cruct::ParserError> {
+                use ::cruct::{ConfigValue, ParserError};
+
+                match value {
+                    ConfigValue::Array(items) if items.len() == #len => Ok(Self(#(#items),*)),
+                    _ => Err(ParserError::TypeMismatch {
+                        field: stringify!(#struct_name).into(),
+                        expected: format!("array of {} items", #len),
+                        found: value.to_string(),
+                    }),
+                }
+            }
+
+            fn type_metadata() -> Option<::cruct::TypeMetadata> {
+                Some(::cruct::TypeMetadata::Tuple(vec![#(#metadata),*]))
+            }
+        }
+    }
+}
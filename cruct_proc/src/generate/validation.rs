@@ -0,0 +1,109 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Type};
+
+use crate::generate::metadata::is_option;
+use crate::parse::{FieldParams, Validation};
+
+/// Wraps the parsing logic of a field with the checks of its validation
+/// parameters, returning a `Validation` error holding the dotted path of the
+/// field when one of them fails.
+///
+/// Optional fields are only checked when set.
+pub fn generate_validations(
+    field: &FieldParams,
+    key: &str,
+    ty: &Type,
+    parse_logic: TokenStream,
+) -> TokenStream {
+    if field
+        .validations
+        .is_empty()
+    {
+        return parse_logic;
+    }
+
+    let checks = field
+        .validations
+        .iter()
+        .map(check_tokens);
+
+    let checks = quote! {
+        #(
+            if let Err(message) = #checks {
+                return Err(::cruct::parser::ParserError::Validation {
+                    field: ctx.path_of(#key),
+                    message,
+                });
+            }
+        )*
+    };
+
+    let checked = if field.optional || is_option(ty) {
+        quote! {
+            if let Some(value) = &value {
+                #checks
+            }
+        }
+    } else {
+        quote! {
+            {
+                let value = &value;
+                #checks
+            }
+        }
+    };
+
+    quote! {
+        {
+            let value = #parse_logic;
+            #checked
+            value
+        }
+    }
+}
+
+/// Generates the expression checking a reference to the value, held in
+/// `value`, which evaluates to a `Result<(), String>`.
+fn check_tokens(validation: &Validation) -> TokenStream {
+    match validation {
+        Validation::Range { min, max } => {
+            let (min, max) = (bound_tokens(min), bound_tokens(max));
+            quote! { ::cruct::validate::range(value, #min, #max) }
+        },
+        Validation::Length { min, max } => {
+            let (min, max) = (bound_tokens(min), bound_tokens(max));
+            quote! { ::cruct::validate::length(value, #min, #max) }
+        },
+        #[cfg(feature = "regex")]
+        Validation::Pattern(pattern) => {
+            quote! {
+                {
+                    static PATTERN: ::std::sync::OnceLock<::cruct::validate::Regex> =
+                        ::std::sync::OnceLock::new();
+
+                    let pattern = PATTERN.get_or_init(|| {
+                        ::cruct::validate::Regex::new(#pattern)
+                            .expect("patterns are checked at compile time")
+                    });
+
+                    ::cruct::validate::pattern(value, pattern)
+                }
+            }
+        },
+        Validation::OneOf(allowed) => {
+            quote! { ::cruct::validate::one_of(value, &[#(#allowed),*]) }
+        },
+        Validation::Custom(path) => {
+            quote! { #path(value).map_err(|e| e.to_string()) }
+        },
+    }
+}
+
+/// Converts an optional bound into the tokens of an `Option`.
+fn bound_tokens(bound: &Option<Expr>) -> TokenStream {
+    match bound {
+        Some(bound) => quote! { Some(#bound) },
+        None => quote! { None },
+    }
+}
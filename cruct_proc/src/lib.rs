@@ -3,10 +3,20 @@
 //! This crate provides the `#[cruct]` attribute macro that generates
 //! configuration loading implementation for structs.
 
-use parse::{MacroParams, StructField, remove_field_attrs};
+use parse::{EnumVariant, MacroParams, StructField, remove_field_attrs, remove_variant_attrs};
 use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{ItemStruct, parse_macro_input};
+use syn::{
+    Error as SynError,
+    Fields,
+    FieldsUnnamed,
+    Item,
+    ItemEnum,
+    ItemStruct,
+    Result as SynResult,
+    parse_macro_input,
+};
 
 mod generate;
 mod parse;
@@ -32,28 +42,163 @@ mod parse;
 ///    Ok(())
 /// }
 /// ```
+///
+/// Enums and tuple structs, such as `struct Port(u16)`, can be annotated as
+/// well, to be used as field types. Enums are written like so:
+/// ```rust ignore
+/// #[cruct(tag = "type", rename_all = "lowercase")]
+/// enum Backend {
+///     S3 { bucket: String, region: String },
+///     Local { path: String },
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn cruct(attr: TokenStream, item: TokenStream) -> TokenStream {
     let params = parse_macro_input!(attr as MacroParams);
-    let mut item = parse_macro_input!(item as ItemStruct);
-
-    let fields = match StructField::from_struct(&item) {
-        Ok(fields) => fields,
-        Err(e) => {
-            return e
-                .to_compile_error()
-                .into();
+    let item = parse_macro_input!(item as Item);
+
+    let expanded = match item {
+        Item::Struct(item) => expand_struct(&params, item),
+        Item::Enum(item) => expand_enum(&params, item),
+        other => {
+            Err(SynError::new_spanned(other, "#[cruct] can only be applied to structs and enums"))
         },
     };
 
+    expanded
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Expands `#[cruct]` on a struct, generating its loader.
+fn expand_struct(params: &MacroParams, mut item: ItemStruct) -> SynResult<TokenStream2> {
+    if params
+        .tag
+        .is_some()
+        || params.insensitive
+    {
+        return Err(SynError::new(
+            Span::call_site(),
+            "`tag` and `insensitive` are only supported on enums",
+        ));
+    }
+
+    if let Fields::Unnamed(fields) = &item.fields {
+        return expand_tuple_struct(params, &item, fields);
+    }
+
+    let fields = StructField::from_struct(&item, params)?;
+
     remove_field_attrs(&mut item);
 
-    let impl_block = generate::generate_impl_block(&item.ident, &params, &fields);
+    let impl_block = generate::generate_impl_block(&item.ident, &item.generics, params, &fields);
 
-    let expanded = quote! {
+    Ok(quote! {
         #item
         #impl_block
-    };
+    })
+}
 
-    expanded.into()
+/// Expands `#[cruct]` on a tuple struct, generating its `FromConfigValue`
+/// implementation.
+fn expand_tuple_struct(
+    params: &MacroParams,
+    item: &ItemStruct,
+    fields: &FieldsUnnamed,
+) -> SynResult<TokenStream2> {
+    if !params
+        .configs
+        .is_empty()
+        || params.interpolate
+        || params
+            .profile_env
+            .is_some()
+        || params
+            .env_prefix
+            .is_some()
+        || params
+            .rename_all
+            .is_some()
+        || params.deny_unknown_fields
+        || params
+            .validate
+            .is_some()
+        || params.default
+    {
+        return Err(SynError::new(
+            Span::call_site(),
+            "`load_config`, `interpolate`, `profile_env`, `env_prefix`, `rename_all`, \
+             `deny_unknown_fields`, `validate` and `default` are not supported on tuple structs",
+        ));
+    }
+
+    if let Some(attr) = fields
+        .unnamed
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| {
+            attr.path()
+                .is_ident("field")
+        })
+    {
+        return Err(SynError::new_spanned(
+            attr,
+            "Field parameters are not supported on tuple struct fields",
+        ));
+    }
+
+    let types = fields
+        .unnamed
+        .iter()
+        .map(|field| {
+            field
+                .ty
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    let tuple_block = generate::generate_tuple_block(&item.ident, &item.generics, &types);
+
+    Ok(quote! {
+        #item
+        #tuple_block
+    })
+}
+
+/// Expands `#[cruct]` on an enum, generating its `FromConfigValue`
+/// implementation.
+fn expand_enum(params: &MacroParams, mut item: ItemEnum) -> SynResult<TokenStream2> {
+    if !params
+        .configs
+        .is_empty()
+        || params.interpolate
+        || params
+            .profile_env
+            .is_some()
+        || params
+            .env_prefix
+            .is_some()
+        || params.deny_unknown_fields
+        || params
+            .validate
+            .is_some()
+        || params.default
+    {
+        return Err(SynError::new(
+            Span::call_site(),
+            "`load_config`, `interpolate`, `profile_env`, `env_prefix`, `deny_unknown_fields`, \
+             `validate` and `default` are only supported on structs",
+        ));
+    }
+
+    let variants = EnumVariant::from_enum(&item, params.rename_all)?;
+
+    remove_variant_attrs(&mut item);
+
+    let enum_block = generate::generate_enum_block(&item.ident, &item.generics, params, &variants);
+
+    Ok(quote! {
+        #item
+        #enum_block
+    })
 }
@@ -0,0 +1,171 @@
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute,
+    Error as SynError,
+    Expr,
+    ExprLit,
+    Fields,
+    Ident,
+    ItemEnum,
+    Lit,
+    MetaNameValue,
+    Result as SynResult,
+    Token,
+    Type,
+};
+
+use super::field_struct::strip_field_attrs;
+use super::{RenameRule, StructField};
+
+/// This represents a parsed enum variant, along with the name it is written
+/// with in the configuration.
+pub struct EnumVariant {
+    /// The identifier of the variant.
+    pub ident: Ident,
+
+    /// The name of the variant in the configuration, which is the `name`
+    /// parameter if set, or the identifier with `rename_all` applied.
+    pub name: String,
+
+    /// The data held by the variant.
+    pub kind: VariantKind,
+}
+
+/// The data held by an enum variant.
+pub enum VariantKind {
+    /// A variant without data, such as `Primary`.
+    Unit,
+
+    /// A variant wrapping a single value, such as `S3(S3Backend)`.
+    Newtype(Type),
+
+    /// A variant holding several unnamed values, such as `Pair(u8, u8)`.
+    Tuple(Vec<Type>),
+
+    /// A variant holding named fields, such as `S3 { bucket: String }`.
+    Struct(Vec<StructField>),
+}
+
+impl EnumVariant {
+    /// Parses all variants of an enum, along with the fields they hold.
+    ///
+    /// ## Parameters
+    /// - `item`: A reference to a parsed `ItemEnum` token stream.
+    /// - `rename_all`: The case convention applied to variant names, if any.
+    pub fn from_enum(item: &ItemEnum, rename_all: Option<RenameRule>) -> SynResult<Vec<Self>> {
+        item.variants
+            .iter()
+            .map(|variant| {
+                let name = match variant_name(&variant.attrs)? {
+                    Some(name) => name,
+                    None => {
+                        let ident = variant
+                            .ident
+                            .to_string();
+
+                        match rename_all {
+                            Some(rule) => rule.apply(&ident),
+                            None => ident,
+                        }
+                    },
+                };
+
+                let kind = match &variant.fields {
+                    Fields::Unit => VariantKind::Unit,
+                    Fields::Named(_) => {
+                        VariantKind::Struct(StructField::from_fields(&variant.fields, None, None)?)
+                    },
+                    Fields::Unnamed(fields) => {
+                        let mut types = fields
+                            .unnamed
+                            .iter()
+                            .map(|field| {
+                                field
+                                    .ty
+                                    .clone()
+                            })
+                            .collect::<Vec<_>>();
+
+                        match types.len() {
+                            1 => VariantKind::Newtype(types.remove(0)),
+                            _ => VariantKind::Tuple(types),
+                        }
+                    },
+                };
+
+                Ok(Self {
+                    ident: variant
+                        .ident
+                        .clone(),
+                    name,
+                    kind,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parses the `name` parameter of a `#[variant(...)]` attribute, if any.
+fn variant_name(attrs: &[Attribute]) -> SynResult<Option<String>> {
+    let mut name = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| is_variant_attr(attr))
+    {
+        let params =
+            attr.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?;
+
+        for param in params {
+            let key = param
+                .path
+                .to_token_stream()
+                .to_string();
+
+            match (key.as_str(), &param.value) {
+                ("name", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    name = Some(value.value());
+                },
+
+                ("name", value) => Err(SynError::new_spanned(
+                    value,
+                    "Invalid value type for 'name' expected '&str'",
+                ))?,
+
+                (key, _) => Err(SynError::new_spanned(
+                    param,
+                    format!("Unknown parameter '{key}'. Known parameters include:\n- name: &str"),
+                ))?,
+            }
+        }
+    }
+
+    Ok(name)
+}
+
+/// Checks if an attribute is a variant attribute.
+///
+/// * `attr`: A reference to an `syn::atr::Attribute` to check.
+fn is_variant_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .is_ident("variant")
+}
+
+/// Removes variant attributes, and the field attributes of the fields they
+/// hold, from an enum.
+///
+/// ## Parameters
+/// - `item`: A mutable reference to an `ItemEnum` from which the attributes
+///   will be removed.
+pub fn remove_variant_attrs(item: &mut ItemEnum) {
+    item.variants
+        .iter_mut()
+        .for_each(|variant| {
+            variant
+                .attrs
+                .retain(|attr| !is_variant_attr(attr));
+
+            strip_field_attrs(&mut variant.fields);
+        });
+}
@@ -1,7 +1,24 @@
+use cruct_shared::MergeStrategy;
+#[cfg(feature = "regex")]
+use cruct_shared::validate::check_pattern;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{Error as SynError, Expr, ExprLit, Lit, MetaNameValue, Result as SynResult, Token};
+use syn::{
+    Error as SynError,
+    Expr,
+    ExprArray,
+    ExprLit,
+    ExprPath,
+    Lit,
+    Meta,
+    Path,
+    Result as SynResult,
+    Token,
+    parse_quote,
+};
+
+use super::Validation;
 
 /// This struct represents a specific field configuration, used along the
 /// `MacroParameters` struct.
@@ -19,19 +36,48 @@ pub struct FieldParams {
     /// found.
     pub env_override: Option<String>,
 
-    /// A default value for the field.
+    /// A default value for the field. Set to `Default::default()` with
+    /// `default` alone, or to a call of the function given as `default_fn`.
     pub default: Option<Expr>,
 
+    /// Whether the field defaults to its value in the `Default`
+    /// implementation of its struct, which is set by `#[cruct(default)]` on
+    /// fields without a default of their own.
+    pub struct_default: bool,
+
     /// Whether the field is optional, meaning it can be omitted
     pub optional: bool,
 
     /// An argument override for the field, used to set the value
     pub arg_override: Option<String>,
+
+    /// How the field's array is merged across configuration sources.
+    pub merge: Option<MergeStrategy>,
+
+    /// A human readable description of the field, taking precedence over
+    /// its doc comments.
+    pub description: Option<String>,
+
+    /// Other keys the field is looked up with, such as its former names.
+    /// Can be given more than once.
+    pub aliases: Vec<String>,
+
+    /// A deprecation message, reported as a warning when one of the aliases
+    /// is set, or the key itself if the field has no aliases.
+    pub deprecated: Option<String>,
+
+    /// Whether the keys of the field's struct are read from the parent
+    /// section instead of a section of its own. Set with `flatten` alone.
+    pub flatten: bool,
+
+    /// The constraints checked once the field is parsed, in the order they
+    /// are declared.
+    pub validations: Vec<Validation>,
 }
 
 impl Parse for FieldParams {
     fn parse(input: ParseStream) -> SynResult<Self> {
-        let params = Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?;
+        let params = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
 
         let mut name = None;
         let mut default = None;
@@ -39,8 +85,59 @@ impl Parse for FieldParams {
         let mut env_override = None;
         let mut arg_override = None;
         let mut optional = false;
+        let mut merge = None;
+        let mut description = None;
+        let mut aliases = Vec::new();
+        let mut deprecated = None;
+        let mut flatten = false;
+        let mut validations = Vec::new();
 
         for param in params {
+            let param = match param {
+                Meta::NameValue(param) => param,
+
+                Meta::Path(path) if path.is_ident("flatten") => {
+                    flatten = true;
+                    continue;
+                },
+
+                Meta::Path(path) if path.is_ident("default") => {
+                    check_default_unset(&default, &path)?;
+                    default = Some(parse_quote! { ::core::default::Default::default() });
+                    continue;
+                },
+
+                Meta::List(list)
+                    if list
+                        .path
+                        .is_ident("range")
+                        || list
+                            .path
+                            .is_ident("length") =>
+                {
+                    validations.push(Validation::from_bounds(&list)?);
+                    continue;
+                },
+
+                other => {
+                    let name = other
+                        .path()
+                        .to_token_stream()
+                        .to_string();
+
+                    let message = match name.as_str() {
+                        "name" | "insensitive" | "env_override" | "arg_override" | "optional"
+                        | "merge" | "description" | "alias" | "deprecated" | "default_fn"
+                        | "range" | "length" | "pattern" | "one_of" | "validate" => {
+                            format!("Missing value for '{name}'")
+                        },
+                        _ => unknown_parameter(&name),
+                    };
+
+                    Err(SynError::new_spanned(other, message))?
+                },
+            };
+
             let key = param
                 .path
                 .to_token_stream()
@@ -67,12 +164,73 @@ impl Parse for FieldParams {
                     optional = value.value();
                 },
 
+                ("merge", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    merge = Some(
+                        value
+                            .value()
+                            .parse::<MergeStrategy>()
+                            .map_err(|e| SynError::new(value.span(), e))?,
+                    );
+                },
+
+                ("description", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    description = Some(value.value());
+                },
+
+                ("alias", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    aliases.push(value.value());
+                },
+
+                ("deprecated", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    deprecated = Some(value.value());
+                },
+
+                ("flatten", Expr::Lit(ExprLit { lit: Lit::Bool(value), .. })) => {
+                    flatten = value.value();
+                },
+
+                ("pattern", Expr::Lit(ExprLit { lit: Lit::Str(value), .. })) => {
+                    #[cfg(not(feature = "regex"))]
+                    return Err(SynError::new(
+                        value.span(),
+                        "The `pattern` parameter requires the `regex` feature of cruct.",
+                    ));
+
+                    #[cfg(feature = "regex")]
+                    {
+                        check_pattern(&value.value())
+                            .map_err(|e| SynError::new(value.span(), e))?;
+                        validations.push(Validation::Pattern(value.value()));
+                    }
+                },
+
+                ("one_of", Expr::Array(ExprArray { elems, .. })) => {
+                    validations.push(Validation::OneOf(
+                        elems
+                            .iter()
+                            .cloned()
+                            .collect(),
+                    ));
+                },
+
+                ("validate", Expr::Path(ExprPath { path, .. })) => {
+                    validations.push(Validation::Custom(path.clone()));
+                },
+
+                ("default_fn", Expr::Path(ExprPath { path, .. })) => {
+                    check_default_unset(&default, &param.path)?;
+                    default = Some(parse_quote! { #path() });
+                },
+
                 ("default", value) => {
+                    check_default_unset(&default, &param.path)?;
                     default = Some(value.clone());
                 },
 
                 (
-                    name @ ("name" | "insensitive" | "env_override" | "arg_override" | "optional"),
+                    name @ ("name" | "insensitive" | "env_override" | "arg_override" | "optional"
+                    | "merge" | "description" | "alias" | "deprecated" | "flatten"
+                    | "range" | "length" | "pattern" | "one_of" | "validate" | "default_fn"),
                     value,
                 ) => {
                     Err(SynError::new_spanned(
@@ -86,6 +244,17 @@ impl Parse for FieldParams {
                                 "arg_override" => "&str",
                                 "env_override" => "&str",
                                 "optional" => "bool",
+                                "merge" => "&str",
+                                "description" => "&str",
+                                "alias" => "&str",
+                                "deprecated" => "&str",
+                                "flatten" => "bool",
+                                "range" => "(min = .., max = ..)",
+                                "length" => "(min = .., max = ..)",
+                                "pattern" => "&str",
+                                "one_of" => "array",
+                                "validate" => "path",
+                                "default_fn" => "path",
 
                                 &_ => panic!(
                                     "Technically, you should not be able to see this error, but \
@@ -97,14 +266,7 @@ impl Parse for FieldParams {
                     ))?
                 },
 
-                (name, _) => Err(SynError::new_spanned(
-                    param,
-                    format!(
-                        "Unknown parameter '{name}'. Known parameters include:\n- name: &str\n- \
-                         insensitive: bool\n- env_override: &str\n- arg_override: &str\n- \
-                         optional: bool"
-                    ),
-                ))?,
+                (name, _) => Err(SynError::new_spanned(param, unknown_parameter(name)))?,
             }
         }
 
@@ -114,7 +276,37 @@ impl Parse for FieldParams {
             env_override,
             arg_override,
             default,
+            struct_default: false,
             optional,
+            merge,
+            description,
+            aliases,
+            deprecated,
+            flatten,
+            validations,
         })
     }
 }
+
+/// Reports a default value set along with another one, as `default` and
+/// `default_fn` are mutually exclusive.
+fn check_default_unset(default: &Option<Expr>, param: &Path) -> SynResult<()> {
+    match default {
+        Some(_) => Err(SynError::new_spanned(
+            param,
+            "A default value is already set, use only one of `default` and `default_fn`",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Returns the error message of an unknown parameter, listing the known ones.
+fn unknown_parameter(name: &str) -> String {
+    format!(
+        "Unknown parameter '{name}'. Known parameters include:\n- name: &str\n- insensitive: \
+         bool\n- env_override: &str\n- arg_override: &str\n- optional: bool\n- merge: &str\n- \
+         description: &str\n- alias: &str\n- deprecated: &str\n- flatten\n- range(min, max)\n- \
+         length(min, max)\n- pattern: &str\n- one_of: array\n- validate: path\n- default\n- \
+         default_fn: path"
+    )
+}
@@ -1,7 +1,19 @@
 use syn::spanned::Spanned;
-use syn::{Attribute, Error as SynError, Ident, ItemStruct, Result as SynResult, Type};
+use syn::{
+    Attribute,
+    Error as SynError,
+    Expr,
+    ExprLit,
+    Fields,
+    Ident,
+    ItemStruct,
+    Lit,
+    Meta,
+    Result as SynResult,
+    Type,
+};
 
-use super::FieldParams;
+use super::{FieldParams, MacroParams, RenameRule};
 
 /// This represents a parsed struct field that optionally might contain
 /// parameters on how to resolve configuration for
@@ -14,11 +26,19 @@ pub struct StructField {
     /// The name of the field as a string.
     pub name: String,
 
+    /// The key used to look up this field in the configuration, which is the
+    /// `name` parameter if set, or the field name with `rename_all` applied.
+    pub key: String,
+
     /// The type of the field.
     pub ty: Type,
 
     /// The identifier of the field.
     pub ident: Ident,
+
+    /// The description of the field, taken from the `description` parameter
+    /// or else from its doc comments.
+    pub description: Option<String>,
 }
 
 impl StructField {
@@ -31,53 +51,202 @@ impl StructField {
     ///
     /// ## Parameters
     /// - `item`: A reference to a parsed `ItemStruct` token stream.
+    /// - `params`: The parameters of the `cruct` macro, whose `rename_all` and
+    ///   `env_prefix` apply to every field. With `default`, fields that are
+    ///   neither optional, flattened nor defaulted default to their value in
+    ///   `Struct::default()`, see `FieldParams::struct_default`.
     ///
     /// ## Returns
     /// A `SynResult` containing a vector of `StructField` instances, each
     /// representing a field in the struct with its associated parameters,
     /// if any.
-    pub fn from_struct(item: &ItemStruct) -> SynResult<Vec<Self>> {
+    pub fn from_struct(item: &ItemStruct, params: &MacroParams) -> SynResult<Vec<Self>> {
+        let mut fields = Self::from_fields(
+            &item.fields,
+            params.rename_all,
+            params
+                .env_prefix
+                .as_deref(),
+        )?;
+
+        if params.default {
+            for field in &mut fields {
+                let params = field
+                    .params
+                    .get_or_insert_with(FieldParams::default);
+
+                params.struct_default = !params.optional
+                    && !params.flatten
+                    && params
+                        .default
+                        .is_none();
+            }
+        }
+
+        Ok(fields)
+    }
+
+    /// Parses named fields, such as the ones of a struct or of a struct-like
+    /// enum variant, extracting any associated parameters.
+    ///
+    /// ## Parameters
+    /// - `item_fields`: The fields to parse.
+    /// - `rename_all`: The case convention applied to the field names, unless
+    ///   overridden by the `name` parameter.
+    /// - `env_prefix`: The prefix of the environment variable derived for
+    ///   fields without an `env_override`, followed by the key in
+    ///   `SCREAMING_SNAKE_CASE`.
+    ///
+    /// Environment variables are derived when either `rename_all` or
+    /// `env_prefix` is set, from the key of the field, so that they follow
+    /// its renaming. Flattened fields derive none, as their own fields do.
+    pub fn from_fields(
+        item_fields: &Fields,
+        rename_all: Option<RenameRule>,
+        env_prefix: Option<&str>,
+    ) -> SynResult<Vec<Self>> {
         let mut fields = Vec::new();
 
-        for field in &item.fields {
+        for field in item_fields {
             let ident = field
                 .ident
                 .as_ref()
                 .ok_or_else(|| SynError::new(field.span(), "Unnamed field not supported"))?;
 
+            if let Some(attr) = field
+                .attrs
+                .iter()
+                .find(|attr| {
+                    attr.path()
+                        .is_ident("description")
+                })
+            {
+                return Err(SynError::new_spanned(
+                    attr,
+                    "The `description` attribute is not supported, use `#[field(description = \
+                     \"...\")]` or doc comments instead.",
+                ));
+            }
+
+            let mut params = field
+                .attrs
+                .iter()
+                .find(|attr| is_field_attr(attr))
+                .map(|attr| attr.parse_args::<FieldParams>())
+                .transpose()?;
+
+            let key = match params
+                .as_ref()
+                .and_then(|p| {
+                    p.name
+                        .clone()
+                }) {
+                Some(name) => name,
+                None => match rename_all {
+                    Some(rule) => rule.apply(&ident.to_string()),
+                    None => ident.to_string(),
+                },
+            };
+
+            let flatten = params
+                .as_ref()
+                .is_some_and(|p| p.flatten);
+
+            if (env_prefix.is_some() || rename_all.is_some()) && !flatten {
+                let params = params.get_or_insert_with(FieldParams::default);
+
+                if params
+                    .env_override
+                    .is_none()
+                {
+                    params.env_override = Some(format!(
+                        "{}{}",
+                        env_prefix.unwrap_or_default(),
+                        RenameRule::ScreamingSnake.apply(&key)
+                    ));
+                }
+            }
+
+            let description = params
+                .as_ref()
+                .and_then(|p| {
+                    p.description
+                        .clone()
+                })
+                .or_else(|| doc_comment(&field.attrs));
+
             fields.push(Self {
                 name: ident.to_string(),
+                key,
                 ident: ident.clone(),
                 ty: field
                     .ty
                     .clone(),
-                params: field
-                    .attrs
-                    .iter()
-                    .find(|attr| is_field_attr(attr))
-                    .map(|attr| attr.parse_args::<FieldParams>())
-                    .transpose()?,
+                params,
+                description,
             });
         }
 
         Ok(fields)
     }
+
+    /// Returns the key used to look up this field in the configuration.
+    pub fn config_key(&self) -> &str {
+        &self.key
+    }
+
+    /// Whether this field defaults to its value in the `Default`
+    /// implementation of its struct, see `FieldParams::struct_default`.
+    pub fn has_struct_default(&self) -> bool {
+        self.params
+            .as_ref()
+            .is_some_and(|p| p.struct_default)
+    }
+
+    /// Whether the keys of this field's struct are read from the parent
+    /// section, see `FieldParams::flatten`.
+    pub fn is_flattened(&self) -> bool {
+        self.params
+            .as_ref()
+            .is_some_and(|p| p.flatten)
+    }
+}
+
+/// Joins the doc comments found in the given attributes into a single line.
+///
+/// Returns `None` if there are no doc comments.
+pub(super) fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(name_value)
+                if name_value
+                    .path
+                    .is_ident("doc") =>
+            {
+                match &name_value.value {
+                    Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => Some(lit.value()),
+                    _ => None,
+                }
+            },
+            _ => None,
+        })
+        .map(|line| {
+            line.trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+
+    (!lines.is_empty()).then(|| lines.join(" "))
 }
 
 /// Checks if an attribute is a field attribute.
 ///
 /// * `attr`: A reference to an `syn::atr::Attribute` to check.
 fn is_field_attr(attr: &Attribute) -> bool {
-    // TODO: use description attribute for metadata purposes
-    let allowed_attrs = ["field", "description"];
-
     attr.path()
-        .get_ident()
-        .is_some_and(|ident| {
-            allowed_attrs
-                .iter()
-                .any(|allowed| ident == allowed)
-        })
+        .is_ident("field")
 }
 
 /// Removes field attributes with the identifier "field" from a struct.
@@ -89,7 +258,12 @@ fn is_field_attr(attr: &Attribute) -> bool {
 /// - `item`: A mutable reference to an `ItemStruct` from which field attributes
 ///   will be removed.
 pub fn remove_field_attrs(item: &mut ItemStruct) {
-    item.fields
+    strip_field_attrs(&mut item.fields);
+}
+
+/// Removes field attributes with the identifier "field" from the given fields.
+pub(super) fn strip_field_attrs(fields: &mut Fields) {
+    fields
         .iter_mut()
         .for_each(|field| {
             field
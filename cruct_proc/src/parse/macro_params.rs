@@ -4,9 +4,20 @@ use cruct_shared::FileFormat;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{Error as SynError, Expr, ExprLit, Lit, Meta, MetaNameValue, Result as SynResult, Token};
+use syn::{
+    Error as SynError,
+    Expr,
+    ExprLit,
+    ExprPath,
+    Lit,
+    Meta,
+    MetaNameValue,
+    Path,
+    Result as SynResult,
+    Token,
+};
 
-use super::ParameterError;
+use super::{ParameterError, RenameRule};
 
 #[derive(Default)]
 pub struct LoadConfig {
@@ -23,6 +34,10 @@ pub struct LoadConfig {
     /// A priority for the configuration file. The lower the number, the
     /// higher the priority.
     pub priority: Option<u8>,
+
+    /// Whether the file is a profile file, made of a `[default]` table and
+    /// one table per profile.
+    pub profiles: bool,
 }
 
 /// This struct represents a parsed version of the `cruct` macro parameters.
@@ -30,13 +45,60 @@ pub struct MacroParams {
     /// A vector of `LoadConfig` structs, each representing a configuration
     /// file to be loaded.
     pub configs: Vec<LoadConfig>,
+
+    /// Whether `${VAR}` placeholders in configuration values should be
+    /// expanded from the environment.
+    pub interpolate: bool,
+
+    /// The environment variable used to select the active profile.
+    pub profile_env: Option<String>,
+
+    /// The key holding the variant name of an internally tagged enum. Enums
+    /// are externally tagged when not set.
+    pub tag: Option<String>,
+
+    /// The case convention applied to the keys of struct fields, or to the
+    /// names of enum variants.
+    pub rename_all: Option<RenameRule>,
+
+    /// The prefix of the environment variables derived for struct fields
+    /// without an `env_override`, such as `APP_` for `APP_HTTP_PORT`.
+    /// Variables are derived without a prefix when only `rename_all` is set.
+    pub env_prefix: Option<String>,
+
+    /// Whether enum variant names are matched case-insensitively.
+    pub insensitive: bool,
+
+    /// Whether keys that no field reads fail loading, instead of being
+    /// reported as warnings. Set with `deny_unknown_fields` alone.
+    pub deny_unknown_fields: bool,
+
+    /// A function checking the struct once every field is built, such as
+    /// `Self::check`, taking a reference to the struct and returning a
+    /// `Result<(), E>`, where `E` implements `Display`.
+    pub validate: Option<Path>,
+
+    /// Whether fields missing from the configuration take their value from
+    /// the `Default` implementation of the struct, unless they have a default
+    /// of their own. Set with `default` alone.
+    pub default: bool,
 }
 
 impl Parse for MacroParams {
     fn parse(input: ParseStream) -> SynResult<Self> {
         let mut configs = Vec::new();
+        let mut interpolate = false;
+        let mut profile_env = None;
+        let mut tag = None;
+        let mut rename_all = None;
+        let mut env_prefix = None;
+        let mut insensitive = false;
+        let mut deny_unknown_fields = false;
+        let mut validate = None;
+        let mut default = false;
 
-        // parse zero or more load_config(...) entries, separated by commas
+        // parse zero or more load_config(...) or key = value entries, separated
+        // by commas
         while !input.is_empty() {
             // parse one Meta, expecting a list: load_config(...)
             let meta = input.parse::<Meta>()?;
@@ -120,6 +182,24 @@ impl Parse for MacroParams {
                                 },
                             },
 
+                            "profiles" => match &name_value.value {
+                                Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. }) => {
+                                    cfg.profiles = lit.value();
+                                },
+                                other => {
+                                    return Err(SynError::new_spanned(
+                                        other,
+                                        ParameterError::InvalidType {
+                                            name: "profiles".to_string(),
+                                            expected: "Boolean".to_string(),
+                                            found: other
+                                                .to_token_stream()
+                                                .to_string(),
+                                        },
+                                    ));
+                                },
+                            },
+
                             other => {
                                 return Err(SynError::new_spanned(
                                     name_value.path,
@@ -140,9 +220,102 @@ impl Parse for MacroParams {
                     }
 
                     configs.push(cfg);
+                },
+
+                Meta::Path(path) if path.is_ident("deny_unknown_fields") => {
+                    deny_unknown_fields = true;
+                },
 
-                    // consume an optional trailing comma
-                    let _ = input.parse::<Token![,]>();
+                Meta::Path(path) if path.is_ident("default") => {
+                    default = true;
+                },
+
+                Meta::NameValue(name_value) => {
+                    let key = name_value
+                        .path
+                        .to_token_stream()
+                        .to_string();
+
+                    match (key.as_str(), &name_value.value) {
+                        ("interpolate", Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. })) => {
+                            interpolate = lit.value();
+                        },
+
+                        ("profile_env", Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) => {
+                            profile_env = Some(lit.value());
+                        },
+
+                        ("tag", Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) => {
+                            tag = Some(lit.value());
+                        },
+
+                        ("rename_all", Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) => {
+                            rename_all = Some(
+                                lit.value()
+                                    .parse::<RenameRule>()
+                                    .map_err(|e| SynError::new(lit.span(), e))?,
+                            );
+                        },
+
+                        ("env_prefix", Expr::Lit(ExprLit { lit: Lit::Str(lit), .. })) => {
+                            env_prefix = Some(lit.value());
+                        },
+
+                        ("insensitive", Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. })) => {
+                            insensitive = lit.value();
+                        },
+
+                        ("deny_unknown_fields", Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. })) => {
+                            deny_unknown_fields = lit.value();
+                        },
+
+                        ("default", Expr::Lit(ExprLit { lit: Lit::Bool(lit), .. })) => {
+                            default = lit.value();
+                        },
+
+                        ("validate", Expr::Path(ExprPath { path, .. })) => {
+                            validate = Some(path.clone());
+                        },
+
+                        (
+                            name @ ("interpolate"
+                            | "profile_env"
+                            | "tag"
+                            | "rename_all"
+                            | "env_prefix"
+                            | "insensitive"
+                            | "deny_unknown_fields"
+                            | "validate"
+                            | "default"),
+                            other,
+                        ) => {
+                            return Err(SynError::new_spanned(
+                                other,
+                                ParameterError::InvalidType {
+                                    name: name.to_string(),
+                                    expected: match name {
+                                        "interpolate"
+                                        | "insensitive"
+                                        | "deny_unknown_fields"
+                                        | "default" => "bool",
+                                        "validate" => "Path",
+                                        _ => "String",
+                                    }
+                                    .to_string(),
+                                    found: other
+                                        .to_token_stream()
+                                        .to_string(),
+                                },
+                            ));
+                        },
+
+                        (other, _) => {
+                            return Err(SynError::new_spanned(
+                                name_value.path,
+                                format!("unknown key '{}' in cruct", other),
+                            ));
+                        },
+                    }
                 },
 
                 other => {
@@ -152,11 +325,25 @@ impl Parse for MacroParams {
                     ));
                 },
             }
+
+            // consume an optional trailing comma
+            let _ = input.parse::<Token![,]>();
         }
 
         // sort by priority (descending)
         configs.sort_by_key(|c| Reverse(c.priority));
 
-        Ok(MacroParams { configs })
+        Ok(MacroParams {
+            configs,
+            interpolate,
+            profile_env,
+            tag,
+            rename_all,
+            env_prefix,
+            insensitive,
+            deny_unknown_fields,
+            validate,
+            default,
+        })
     }
 }
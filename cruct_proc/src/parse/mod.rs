@@ -1,15 +1,21 @@
 use thiserror::Error as ThisError;
 
+mod enum_variant;
 mod field_params;
 mod field_struct;
 mod macro_params;
+mod rename;
+mod validation;
 
 #[cfg(test)]
 mod tests;
 
+pub use enum_variant::{EnumVariant, VariantKind, remove_variant_attrs};
 pub use field_params::FieldParams;
 pub use field_struct::{StructField, remove_field_attrs};
 pub use macro_params::MacroParams;
+pub use rename::RenameRule;
+pub use validation::Validation;
 
 /// This enum is an error representation for parameter parsing. It implements
 /// Display for error descriptions.
@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+/// A case convention applied to the keys of struct fields or the names of
+/// enum variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`, the name in lower case.
+    Lower,
+
+    /// `UPPERCASE`, the name in upper case.
+    Upper,
+
+    /// `PascalCase`.
+    Pascal,
+
+    /// `camelCase`.
+    Camel,
+
+    /// `snake_case`.
+    Snake,
+
+    /// `SCREAMING_SNAKE_CASE`.
+    ScreamingSnake,
+
+    /// `kebab-case`.
+    Kebab,
+
+    /// `SCREAMING-KEBAB-CASE`.
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Applies the convention to a name written in `PascalCase` or
+    /// `snake_case`.
+    pub fn apply(&self, name: &str) -> String {
+        let words = words(name);
+
+        let capitalized = || {
+            words
+                .iter()
+                .map(|word| capitalize(word))
+                .collect::<Vec<_>>()
+        };
+
+        match self {
+            RenameRule::Lower => name.to_lowercase(),
+            RenameRule::Upper => name.to_uppercase(),
+            RenameRule::Pascal => capitalized().concat(),
+            RenameRule::Camel => {
+                let mut camel = capitalized();
+
+                if let Some(first) = camel.first_mut() {
+                    *first = first.to_lowercase();
+                }
+
+                camel.concat()
+            },
+            RenameRule::Snake => words.join("_"),
+            RenameRule::ScreamingSnake => words
+                .join("_")
+                .to_uppercase(),
+            RenameRule::Kebab => words.join("-"),
+            RenameRule::ScreamingKebab => words
+                .join("-")
+                .to_uppercase(),
+        }
+    }
+}
+
+/// Splits a name into lower case words, at underscores, dashes and case
+/// changes, such as `http`, `server` and `v2` for `HTTPServerV2`.
+fn words(name: &str) -> Vec<String> {
+    let chars = name
+        .chars()
+        .collect::<Vec<_>>();
+
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars
+        .iter()
+        .enumerate()
+    {
+        if c == '_' || c == '-' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+
+            continue;
+        }
+
+        let previous = i
+            .checked_sub(1)
+            .map(|i| chars[i]);
+        let next = chars
+            .get(i + 1)
+            .copied();
+
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(char::is_lowercase))
+            });
+
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+
+        current.extend(c.to_lowercase());
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+/// Upper cases the first character of a word.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars)
+            .collect(),
+        None => String::new(),
+    }
+}
+
+impl FromStr for RenameRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            other => {
+                return Err(format!(
+                    "'{other}' is not a valid case convention, expected one of: lowercase, \
+                     UPPERCASE, PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE, \
+                     kebab-case, SCREAMING-KEBAB-CASE"
+                ));
+            },
+        })
+    }
+}
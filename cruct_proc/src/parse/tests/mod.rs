@@ -1,3 +1,5 @@
 mod test_field_params;
+mod test_field_struct;
 mod test_macro_params;
+mod test_rename;
 mod test_value_mismatch;
@@ -1,6 +1,8 @@
+use cruct_shared::MergeStrategy;
+use quote::ToTokens;
 use syn::{Result, parse_str};
 
-use crate::parse::FieldParams;
+use crate::parse::{FieldParams, Validation};
 
 #[test]
 fn name_invalid_value() {
@@ -60,8 +62,188 @@ fn unknown_key() {
         assert_eq!(
             e.to_string(),
             "Unknown parameter 'unknown'. Known parameters include:\n- name: &str\n- insensitive: \
-             bool\n- env_override: &str\n- arg_override: &str\n- optional: bool"
+             bool\n- env_override: &str\n- arg_override: &str\n- optional: bool\n- merge: &str\n- \
+             description: &str\n- alias: &str\n- deprecated: &str\n- flatten\n- range(min, \
+             max)\n- length(min, max)\n- pattern: &str\n- one_of: array\n- validate: path\n- \
+             default\n- default_fn: path"
                 .to_string()
         );
     }
 }
+
+#[test]
+fn merge_strategy() {
+    let src = r#"merge = "by_key(name)""#;
+    let params: FieldParams = parse_str(src).unwrap();
+
+    assert_eq!(params.merge, Some(MergeStrategy::ByKey("name".into())));
+}
+
+#[test]
+fn merge_invalid_strategy() {
+    let src = r#"merge = "shuffle""#;
+    let params: Result<FieldParams> = parse_str(src);
+
+    if let Err(e) = params {
+        assert_eq!(e.to_string(), "'shuffle' is not a valid merge strategy".to_string());
+    }
+}
+
+#[test]
+fn description_invalid_value() {
+    let src = r#"description = 1"#;
+    let params: Result<FieldParams> = parse_str(src);
+
+    if let Err(e) = params {
+        assert_eq!(
+            e.to_string(),
+            "Invalid value type for 'description' expected '&str'".to_string()
+        );
+    }
+}
+
+#[test]
+fn description_is_parsed() {
+    let params: FieldParams = parse_str(r#"description = "The port to listen on.""#).unwrap();
+
+    assert_eq!(
+        params
+            .description
+            .as_deref(),
+        Some("The port to listen on.")
+    );
+}
+
+#[test]
+fn aliases_and_deprecation_are_parsed() {
+    let params: FieldParams =
+        parse_str(r#"alias = "port", alias = "listen_port", deprecated = "use http_port""#)
+            .unwrap();
+
+    assert_eq!(params.aliases, ["port", "listen_port"]);
+    assert_eq!(
+        params
+            .deprecated
+            .as_deref(),
+        Some("use http_port")
+    );
+}
+
+#[test]
+fn flatten_is_parsed() {
+    let params: FieldParams = parse_str("flatten").unwrap();
+    assert!(params.flatten);
+
+    let params: FieldParams = parse_str("flatten = false").unwrap();
+    assert!(!params.flatten);
+}
+
+#[test]
+fn missing_value() {
+    let params: Result<FieldParams> = parse_str("optional");
+
+    assert_eq!(
+        params
+            .err()
+            .unwrap()
+            .to_string(),
+        "Missing value for 'optional'"
+    );
+}
+
+#[test]
+fn validations_are_parsed_in_order() {
+    let params: FieldParams = parse_str(
+        r#"range(min = 1, max = 65535), length(min = 1), one_of = ["a", "b"], validate = checks::port"#,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        params.validations[..],
+        [
+            Validation::Range { min: Some(_), max: Some(_) },
+            Validation::Length { min: Some(_), max: None },
+            Validation::OneOf(_),
+            Validation::Custom(_),
+        ]
+    ));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern_is_parsed() {
+    let params: FieldParams = parse_str(r#"pattern = "^[a-z]+$""#).unwrap();
+
+    assert!(matches!(
+        &params.validations[..],
+        [Validation::Pattern(pattern)] if pattern == "^[a-z]+$"
+    ));
+}
+
+#[cfg(not(feature = "regex"))]
+#[test]
+fn pattern_requires_regex_feature() {
+    let params: Result<FieldParams> = parse_str(r#"pattern = "^[a-z]+$""#);
+
+    assert!(params.is_err_and(|e| {
+        e.to_string()
+            .contains("requires the `regex` feature")
+    }));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn invalid_pattern() {
+    let params: Result<FieldParams> = parse_str(r#"pattern = "[a-z""#);
+
+    assert!(params.is_err_and(|e| {
+        e.to_string()
+            .contains("unclosed character class")
+    }));
+}
+
+#[test]
+fn bounds_are_required() {
+    let params: Result<FieldParams> = parse_str("range()");
+
+    assert_eq!(
+        params
+            .err()
+            .unwrap()
+            .to_string(),
+        "Expected at least one of 'min' and 'max'"
+    );
+}
+
+#[test]
+fn default_forms_are_parsed() {
+    let default = |src| {
+        parse_str::<FieldParams>(src)
+            .unwrap()
+            .default
+            .unwrap()
+            .to_token_stream()
+            .to_string()
+    };
+
+    assert_eq!(default("default"), ":: core :: default :: Default :: default ()");
+    assert_eq!(default("default_fn = defaults::port"), "defaults :: port ()");
+    assert_eq!(default("default = 8080"), "8080");
+}
+
+#[test]
+fn defaults_are_exclusive() {
+    for src in
+        ["default, default_fn = port", "default_fn = port, default = 1", "default = 1, default"]
+    {
+        let params: Result<FieldParams> = parse_str(src);
+
+        assert_eq!(
+            params
+                .err()
+                .unwrap()
+                .to_string(),
+            "A default value is already set, use only one of `default` and `default_fn`"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use syn::{ItemStruct, parse_quote, parse_str};
+
+use crate::parse::{MacroParams, StructField};
+
+fn params() -> MacroParams {
+    parse_str("").unwrap()
+}
+
+#[test]
+fn description_attribute_is_rejected() {
+    let item: ItemStruct = parse_quote! {
+        struct Config {
+            #[description(name = "port")]
+            port: u16,
+        }
+    };
+
+    let Err(e) = StructField::from_struct(&item, &params()) else {
+        panic!("expected the description attribute to be rejected");
+    };
+
+    assert!(
+        e.to_string()
+            .contains("use `#[field(description = \"...\")]`")
+    );
+}
+
+#[test]
+fn description_is_taken_from_field_or_doc_comments() {
+    let item: ItemStruct = parse_quote! {
+        struct Config {
+            /// The port to listen on.
+            port: u16,
+
+            /// Ignored.
+            #[field(description = "The host to bind.")]
+            host: String,
+        }
+    };
+
+    let fields = StructField::from_struct(&item, &params()).unwrap();
+
+    assert_eq!(
+        fields[0]
+            .description
+            .as_deref(),
+        Some("The port to listen on.")
+    );
+    assert_eq!(
+        fields[1]
+            .description
+            .as_deref(),
+        Some("The host to bind.")
+    );
+}
+
+#[test]
+fn env_names_follow_the_renamed_key() {
+    let item: ItemStruct = parse_quote! {
+        struct Config {
+            http_port: u16,
+
+            #[field(name = "db")]
+            database_url: String,
+        }
+    };
+
+    let env = |params: &MacroParams| {
+        StructField::from_struct(&item, params)
+            .unwrap()
+            .into_iter()
+            .map(|field| {
+                field
+                    .params
+                    .and_then(|p| p.env_override)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(env(&params()), [None, None]);
+    assert_eq!(
+        env(&parse_str(r#"rename_all = "camelCase""#).unwrap()),
+        [Some("HTTP_PORT".into()), Some("DB".into())]
+    );
+    assert_eq!(
+        env(&parse_str(r#"rename_all = "kebab-case", env_prefix = "APP_""#).unwrap()),
+        [Some("APP_HTTP_PORT".into()), Some("APP_DB".into())]
+    );
+}
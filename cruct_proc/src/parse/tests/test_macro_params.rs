@@ -1,6 +1,7 @@
+use quote::ToTokens;
 use syn::{Result, parse_str};
 
-use crate::parse::MacroParams;
+use crate::parse::{MacroParams, RenameRule};
 
 #[test]
 fn parse_single_load_config() {
@@ -28,6 +29,16 @@ fn parse_single_load_config() {
     );
 }
 
+#[test]
+fn load_config_profiles() {
+    let params: MacroParams =
+        parse_str(r#"load_config(path = "a.toml", profiles = true)"#).unwrap();
+    assert!(params.configs[0].profiles);
+
+    let params: MacroParams = parse_str(r#"load_config(path = "a.toml")"#).unwrap();
+    assert!(!params.configs[0].profiles);
+}
+
 #[test]
 fn load_config_without_required_parameter() {
     let src = r#"load_config()"#;
@@ -112,3 +123,113 @@ fn parse_invalid_file_format() {
         );
     }
 }
+
+#[test]
+fn parse_interpolate_flag() {
+    let src = r#"load_config(path = "a.toml"), interpolate = true"#;
+    let params: MacroParams = parse_str(src).unwrap();
+
+    assert!(params.interpolate);
+    assert_eq!(
+        params
+            .configs
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn parse_profile_env() {
+    let src = r#"profile_env = "APP_PROFILE", load_config(path = "a.toml")"#;
+    let params: MacroParams = parse_str(src).unwrap();
+
+    assert_eq!(
+        params
+            .profile_env
+            .as_deref(),
+        Some("APP_PROFILE")
+    );
+}
+
+#[test]
+fn parse_unknown_macro_key() {
+    let src = r#"unknown = true"#;
+    let params: Result<MacroParams> = parse_str(src);
+
+    if let Err(e) = params {
+        assert_eq!(e.to_string(), "unknown key 'unknown' in cruct".to_string());
+    }
+}
+
+#[test]
+fn parse_enum_options() {
+    let src = r#"tag = "type", rename_all = "kebab-case", insensitive = true"#;
+    let params: MacroParams = parse_str(src).unwrap();
+
+    assert_eq!(
+        params
+            .tag
+            .as_deref(),
+        Some("type")
+    );
+    assert_eq!(params.rename_all, Some(RenameRule::Kebab));
+    assert!(params.insensitive);
+}
+
+#[test]
+fn parse_invalid_rename_rule() {
+    let src = r#"rename_all = "Title Case""#;
+    let params: Result<MacroParams> = parse_str(src);
+
+    assert!(params.is_err_and(|e| {
+        e.to_string()
+            .starts_with("'Title Case' is not a valid case convention")
+    }));
+}
+
+#[test]
+fn parse_struct_naming_options() {
+    let src = r#"rename_all = "SCREAMING_SNAKE_CASE", env_prefix = "APP_""#;
+    let params: MacroParams = parse_str(src).unwrap();
+
+    assert_eq!(params.rename_all, Some(RenameRule::ScreamingSnake));
+    assert_eq!(
+        params
+            .env_prefix
+            .as_deref(),
+        Some("APP_")
+    );
+}
+
+#[test]
+fn parse_deny_unknown_fields() {
+    let params: MacroParams = parse_str("deny_unknown_fields").unwrap();
+    assert!(params.deny_unknown_fields);
+
+    let params: MacroParams = parse_str("deny_unknown_fields = false").unwrap();
+    assert!(!params.deny_unknown_fields);
+}
+
+#[test]
+fn parse_validate() {
+    let params: MacroParams = parse_str("validate = Self::check").unwrap();
+
+    assert_eq!(
+        params
+            .validate
+            .map(|path| {
+                path.to_token_stream()
+                    .to_string()
+            })
+            .as_deref(),
+        Some("Self :: check")
+    );
+}
+
+#[test]
+fn parse_struct_default() {
+    let params: MacroParams = parse_str("default, deny_unknown_fields").unwrap();
+
+    assert!(params.default);
+    assert!(params.deny_unknown_fields);
+}
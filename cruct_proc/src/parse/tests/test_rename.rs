@@ -0,0 +1,33 @@
+use crate::parse::RenameRule;
+
+#[test]
+fn renames_pascal_case_variants() {
+    let cases = [
+        (RenameRule::Lower, "readreplica"),
+        (RenameRule::Upper, "READREPLICA"),
+        (RenameRule::Pascal, "ReadReplica"),
+        (RenameRule::Camel, "readReplica"),
+        (RenameRule::Snake, "read_replica"),
+        (RenameRule::ScreamingSnake, "READ_REPLICA"),
+        (RenameRule::Kebab, "read-replica"),
+        (RenameRule::ScreamingKebab, "READ-REPLICA"),
+    ];
+
+    for (rule, expected) in cases {
+        assert_eq!(rule.apply("ReadReplica"), expected, "{rule:?}");
+    }
+}
+
+#[test]
+fn renames_snake_case_names() {
+    assert_eq!(RenameRule::Camel.apply("http_port"), "httpPort");
+    assert_eq!(RenameRule::Kebab.apply("http_port"), "http-port");
+    assert_eq!(RenameRule::Lower.apply("http_port"), "http_port");
+}
+
+#[test]
+fn splits_acronyms_and_digits() {
+    assert_eq!(RenameRule::Snake.apply("HTTPServerV2"), "http_server_v2");
+    assert_eq!(RenameRule::Kebab.apply("S3"), "s3");
+    assert_eq!(RenameRule::Camel.apply("IOError"), "ioError");
+}
@@ -41,3 +41,30 @@ fn priority_value_mismatch() {
         );
     }
 }
+
+#[test]
+fn interpolate_value_mismatch() {
+    let src = r#"interpolate = "yes""#;
+    let params: Result<MacroParams> = parse_str(src);
+
+    if let Err(e) = params {
+        assert_eq!(
+            e.to_string(),
+            "Invalid parameter type for 'interpolate', expected 'bool', found '\"yes\"'"
+                .to_string()
+        );
+    }
+}
+
+#[test]
+fn profile_env_value_mismatch() {
+    let src = r#"profile_env = 1"#;
+    let params: Result<MacroParams> = parse_str(src);
+
+    if let Err(e) = params {
+        assert_eq!(
+            e.to_string(),
+            "Invalid parameter type for 'profile_env', expected 'String', found '1'".to_string()
+        );
+    }
+}
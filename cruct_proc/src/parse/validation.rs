@@ -0,0 +1,66 @@
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::{Error as SynError, Expr, MetaList, MetaNameValue, Path, Result as SynResult, Token};
+
+/// A constraint checked once a field is parsed, declared with one of the
+/// validation parameters of `#[field]`.
+pub enum Validation {
+    /// `range(min = 1, max = 65535)`, the inclusive bounds of the value.
+    Range { min: Option<Expr>, max: Option<Expr> },
+
+    /// `length(min = 1)`, the inclusive bounds of the length of the value.
+    Length { min: Option<Expr>, max: Option<Expr> },
+
+    /// `pattern = "^[a-z]+$"`, a regular expression the value must match.
+    #[cfg(feature = "regex")]
+    Pattern(String),
+
+    /// `one_of = ["debug", "info"]`, the accepted values.
+    OneOf(Vec<Expr>),
+
+    /// `validate = path::to_fn`, a function taking a reference to the value
+    /// and returning a `Result<(), E>`, where `E` implements `Display`.
+    Custom(Path),
+}
+
+impl Validation {
+    /// Parses a `range(...)` or `length(...)` parameter, holding a `min`
+    /// bound, a `max` bound or both.
+    pub fn from_bounds(list: &MetaList) -> SynResult<Self> {
+        let pairs: Punctuated<MetaNameValue, Token![,]> =
+            list.parse_args_with(Punctuated::parse_terminated)?;
+
+        let mut min = None;
+        let mut max = None;
+
+        for pair in pairs {
+            let key = pair
+                .path
+                .to_token_stream()
+                .to_string();
+
+            match key.as_str() {
+                "min" => min = Some(pair.value),
+                "max" => max = Some(pair.value),
+                other => {
+                    return Err(SynError::new_spanned(
+                        pair.path,
+                        format!("Unknown bound '{other}', expected 'min' or 'max'"),
+                    ));
+                },
+            }
+        }
+
+        if min.is_none() && max.is_none() {
+            return Err(SynError::new_spanned(list, "Expected at least one of 'min' and 'max'"));
+        }
+
+        match list
+            .path
+            .is_ident("range")
+        {
+            true => Ok(Validation::Range { min, max }),
+            false => Ok(Validation::Length { min, max }),
+        }
+    }
+}
@@ -1,5 +1,34 @@
+pub mod metadata;
 pub mod parser;
 pub mod source;
+pub mod validate;
 
+pub use metadata::{
+    DefaultValue,
+    FieldMetadata,
+    TypeMetadata,
+    VariantContent,
+    VariantMetadata,
+    example_config,
+    json_schema,
+    markdown_reference,
+};
 pub use parser::{ConfigValue, FileFormat, FromConfigValue, Parser, ParserError, get_parser};
-pub use source::{CliSource, ConfigBuilder, ConfigFileSource, ConfigSource};
+#[cfg(feature = "clap")]
+pub use source::{ClapSource, ConfigArgs};
+pub use source::{
+    CliDefinition,
+    CliFlag,
+    CliSource,
+    ConfigBuilder,
+    ConfigFileSource,
+    ConfigSource,
+    ConfigWarning,
+    EnvProvider,
+    JsonPatchSource,
+    LoadContext,
+    MergePatchSource,
+    MergeStrategy,
+    interpolate,
+    merge_patch,
+};
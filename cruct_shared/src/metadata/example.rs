@@ -0,0 +1,292 @@
+#[cfg(feature = "json")]
+use super::json::Json;
+use super::json::quote;
+use super::{DefaultValue, FieldMetadata, split_type};
+use crate::FileFormat;
+
+/// Renders an example configuration file in the given format, as returned by
+/// the generated `Struct::example_config()`.
+///
+/// Every key is listed along with a comment holding its description, using
+/// its default value when it is a literal, and a `"<type>"` placeholder for
+/// required fields. Optional fields and fields defaulting to an expression
+/// are commented out. JSON has no comments, so descriptions are left out and
+/// keys that would be commented out are omitted.
+///
+/// * `fields`: The fields of the configuration.
+/// * `format`: The format of the file.
+pub fn example_config(fields: &[FieldMetadata], format: FileFormat) -> String {
+    let entries = entries(fields);
+
+    match format {
+        #[cfg(feature = "toml")]
+        FileFormat::Toml => {
+            let mut out = String::new();
+            render_toml(&entries, &[], false, &mut out);
+            out.trim_start()
+                .to_string()
+        },
+
+        #[cfg(feature = "yaml")]
+        FileFormat::Yml => {
+            let mut lines = Vec::new();
+            render_yaml(&entries, 0, false, &mut lines);
+            lines.join("\n") + "\n"
+        },
+
+        #[cfg(feature = "json")]
+        FileFormat::Json => format!("{}\n", to_json(&entries)),
+    }
+}
+
+/// A key of the example file.
+struct Entry {
+    key: &'static str,
+
+    /// The comment shown above the key, if any.
+    comment: Option<String>,
+
+    /// Whether the key is commented out, as it can be left unset.
+    commented: bool,
+
+    value: Node,
+}
+
+/// The value of a key of the example file.
+enum Node {
+    /// A value written inline, already rendered, such as `8080` or `["a"]`.
+    ///
+    /// The syntax of literals, strings and inline arrays is shared by TOML,
+    /// YAML and JSON.
+    Inline(String),
+
+    /// A nested section.
+    Table(Vec<Entry>),
+
+    /// An array of sections, shown with a single item.
+    TableArray(Vec<Entry>),
+}
+
+/// Builds the entries describing the given fields.
+fn entries(fields: &[FieldMetadata]) -> Vec<Entry> {
+    fields
+        .iter()
+        .map(|field| {
+            let expr = match &field.default {
+                Some(DefaultValue::Expr(expr)) => Some(*expr),
+                _ => None,
+            };
+
+            let mut details = field
+                .description
+                .map(str::to_string)
+                .into_iter()
+                .collect::<Vec<_>>();
+
+            if let Some(expr) = expr {
+                details.push(format!("[default: {expr}]"));
+            }
+
+            if !field
+                .variants
+                .is_empty()
+            {
+                details.push(format!(
+                    "[one of: {}]",
+                    field
+                        .variants
+                        .join(", ")
+                ));
+            }
+
+            if let Some(env) = field.env {
+                details.push(format!("[env: {env}]"));
+            }
+
+            Entry {
+                key: field.key,
+                comment: (!details.is_empty()).then(|| details.join(" ")),
+                commented: field.optional || expr.is_some(),
+                value: node(field.ty, field),
+            }
+        })
+        .collect()
+}
+
+/// Builds the value of a field of the given type.
+fn node(ty: &str, field: &FieldMetadata) -> Node {
+    match split_type(ty) {
+        ("Option", Some(inner)) => node(inner, field),
+        ("Vec", Some(_))
+            if !field
+                .fields
+                .is_empty() =>
+        {
+            Node::TableArray(entries(&field.fields))
+        },
+        _ if !field
+            .fields
+            .is_empty() =>
+        {
+            Node::Table(entries(&field.fields))
+        },
+        _ => Node::Inline(match &field.default {
+            Some(DefaultValue::Bool(b)) => b.to_string(),
+            Some(DefaultValue::Integer(i)) => i.to_string(),
+            Some(DefaultValue::Float(x)) => format!("{x:?}"),
+            Some(DefaultValue::Str(s)) => quote(s),
+            _ => placeholder(ty),
+        }),
+    }
+}
+
+/// Renders the placeholder of a value of the given type, such as `"<u16>"`
+/// or `["<String>"]`.
+fn placeholder(ty: &str) -> String {
+    match split_type(ty) {
+        ("Option", Some(inner)) => placeholder(inner),
+        ("Vec", Some(inner)) => format!("[{}]", placeholder(inner)),
+        _ => quote(&format!("<{ty}>")),
+    }
+}
+
+/// Renders the entries of a TOML table, inline values first and nested
+/// tables after them.
+///
+/// * `path`: The keys of the table.
+/// * `commented`: Whether the whole table is commented out.
+#[cfg(feature = "toml")]
+fn render_toml(entries: &[Entry], path: &[&str], commented: bool, out: &mut String) {
+    let prefix = |entry: &Entry| {
+        if commented || entry.commented { "# " } else { "" }
+    };
+
+    for entry in entries {
+        if let Node::Inline(value) = &entry.value {
+            if let Some(comment) = &entry.comment {
+                out.push_str(&format!("\n# {comment}\n"));
+            }
+
+            out.push_str(&format!("{}{} = {value}\n", prefix(entry), toml_key(entry.key)));
+        }
+    }
+
+    for entry in entries {
+        let (entries, header) = match &entry.value {
+            Node::Inline(_) => continue,
+            Node::Table(entries) => (entries, ("[", "]")),
+            Node::TableArray(entries) => (entries, ("[[", "]]")),
+        };
+
+        let path = path
+            .iter()
+            .copied()
+            .chain([entry.key])
+            .collect::<Vec<_>>();
+
+        let name = path
+            .iter()
+            .map(|key| toml_key(key))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        out.push('\n');
+
+        if let Some(comment) = &entry.comment {
+            out.push_str(&format!("# {comment}\n"));
+        }
+
+        out.push_str(&format!("{}{}{name}{}\n", prefix(entry), header.0, header.1));
+        render_toml(entries, &path, commented || entry.commented, out);
+    }
+}
+
+/// Quotes a TOML key unless it is a bare key.
+#[cfg(feature = "toml")]
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if bare { key.to_string() } else { quote(key) }
+}
+
+/// Renders the entries of a YAML mapping, one line each.
+///
+/// * `indent`: The indentation of the mapping.
+/// * `commented`: Whether the whole mapping is commented out.
+#[cfg(feature = "yaml")]
+fn render_yaml(entries: &[Entry], indent: usize, commented: bool, lines: &mut Vec<String>) {
+    for entry in entries {
+        let pad = " ".repeat(indent);
+        let prefix = if commented || entry.commented { "# " } else { "" };
+        let key = yaml_key(entry.key);
+
+        if let Some(comment) = &entry.comment {
+            lines.push(format!("{pad}# {comment}"));
+        }
+
+        match &entry.value {
+            Node::Inline(value) => lines.push(format!("{pad}{prefix}{key}: {value}")),
+            Node::Table(entries) if entries.is_empty() => {
+                lines.push(format!("{pad}{prefix}{key}: {{}}"))
+            },
+            Node::Table(entries) => {
+                lines.push(format!("{pad}{prefix}{key}:"));
+                render_yaml(entries, indent + 2, commented || entry.commented, lines);
+            },
+            Node::TableArray(entries) => {
+                lines.push(format!("{pad}{prefix}{key}:"));
+
+                let start = lines.len();
+                render_yaml(entries, indent + 4, commented || entry.commented, lines);
+
+                // The first line of the item starts the sequence entry.
+                match lines.get_mut(start) {
+                    Some(first) => first.replace_range(indent + 2..indent + 4, "- "),
+                    None => lines.push(format!("{pad}{prefix}  - {{}}")),
+                }
+            },
+        }
+    }
+}
+
+/// Quotes a YAML key unless it can be written plainly.
+#[cfg(feature = "yaml")]
+fn yaml_key(key: &str) -> String {
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+
+    if plain { key.to_string() } else { quote(key) }
+}
+
+/// Converts the entries into a JSON object, leaving out commented keys.
+#[cfg(feature = "json")]
+fn to_json(entries: &[Entry]) -> Json {
+    Json::Object(
+        entries
+            .iter()
+            .filter(|entry| !entry.commented)
+            .map(|entry| {
+                let value = match &entry.value {
+                    Node::Inline(value) => Json::Raw(value.clone()),
+                    Node::Table(entries) => to_json(entries),
+                    Node::TableArray(entries) => Json::Array(vec![to_json(entries)]),
+                };
+
+                (
+                    entry
+                        .key
+                        .to_string(),
+                    value,
+                )
+            })
+            .collect(),
+    )
+}
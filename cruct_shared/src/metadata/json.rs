@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter, Result as DisplayResult, Write};
+
+/// A JSON document, rendered with [`Display`].
+///
+/// Object members keep the order they were inserted in, so generated
+/// documents are stable and read top to bottom like the struct they describe.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+
+    /// A value already rendered as JSON.
+    #[cfg(feature = "json")]
+    Raw(String),
+}
+
+impl Json {
+    /// Creates a string value.
+    pub(crate) fn string(s: impl Into<String>) -> Self {
+        Json::String(s.into())
+    }
+
+    /// Creates an object from its members.
+    pub(crate) fn object<const N: usize>(members: [(&str, Json); N]) -> Self {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Appends a member, if this is an object.
+    pub(crate) fn insert(&mut self, key: &str, value: Json) {
+        if let Json::Object(members) = self {
+            members.push((key.to_string(), value));
+        }
+    }
+
+    /// Renders the value, indenting nested values by two spaces per level.
+    fn write(&self, out: &mut String, indent: usize) -> DisplayResult {
+        match self {
+            Json::Bool(b) => write!(out, "{b}"),
+            Json::Number(n) => out.write_str(n),
+            #[cfg(feature = "json")]
+            Json::Raw(raw) => out.write_str(raw),
+            Json::String(s) => write_string(out, s),
+            Json::Array(items) if items.is_empty() => out.write_str("[]"),
+            Json::Array(items) => {
+                out.write_str("[\n")?;
+
+                for (i, item) in items
+                    .iter()
+                    .enumerate()
+                {
+                    write!(out, "{:1$}", "", indent + 2)?;
+                    item.write(out, indent + 2)?;
+                    out.write_str(if i + 1 < items.len() { ",\n" } else { "\n" })?;
+                }
+
+                write!(out, "{:1$}]", "", indent)
+            },
+            Json::Object(members) if members.is_empty() => out.write_str("{}"),
+            Json::Object(members) => {
+                out.write_str("{\n")?;
+
+                for (i, (key, value)) in members
+                    .iter()
+                    .enumerate()
+                {
+                    write!(out, "{:1$}", "", indent + 2)?;
+                    write_string(out, key)?;
+                    out.write_str(": ")?;
+                    value.write(out, indent + 2)?;
+                    out.write_str(if i + 1 < members.len() { ",\n" } else { "\n" })?;
+                }
+
+                write!(out, "{:1$}}}", "", indent)
+            },
+        }
+    }
+}
+
+/// Quotes a string, escaping it as required.
+///
+/// The escapes are valid in TOML basic strings and YAML double quoted
+/// strings as well.
+pub(crate) fn quote(s: &str) -> String {
+    let mut out = String::new();
+    write_string(&mut out, s).expect("writing to a String never fails");
+    out
+}
+
+/// Writes a quoted JSON string, escaping it as required.
+fn write_string(out: &mut String, s: &str) -> DisplayResult {
+    out.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+
+    out.write_char('"')
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> DisplayResult {
+        let mut out = String::new();
+        self.write(&mut out, 0)?;
+        f.write_str(&out)
+    }
+}
@@ -0,0 +1,124 @@
+use super::{DefaultValue, FieldMetadata, split_type};
+
+/// Renders a Markdown reference of a configuration, as returned by the
+/// generated `Struct::markdown_reference()`.
+///
+/// The reference is a table listing every key, nested ones included, along
+/// with its type, default value, environment variable, command-line flag
+/// and description. Keys of the items of arrays of sections are written
+/// as `key[].item`.
+///
+/// * `fields`: The fields of the configuration.
+pub fn markdown_reference(fields: &[FieldMetadata]) -> String {
+    let mut rows = Vec::new();
+    collect_rows(fields, "", true, &mut rows);
+
+    let mut out = String::from(
+        "| Key | Type | Default | Env | Flag | Description |\n| --- | --- | --- | --- | --- | --- \
+         |\n",
+    );
+
+    for row in rows {
+        out.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+
+    out
+}
+
+/// Adds a row for each of the given fields and their nested fields.
+///
+/// * `prefix`: The key path of the section holding the fields.
+/// * `flags`: Whether the fields can be set from the command line, which items
+///   of arrays cannot.
+fn collect_rows(fields: &[FieldMetadata], prefix: &str, flags: bool, rows: &mut Vec<[String; 6]>) {
+    for field in fields {
+        let path = format!("{prefix}{}", field.key);
+        let nested = !field
+            .fields
+            .is_empty();
+
+        let default = match &field.default {
+            Some(default) => code(&default_text(default)),
+            None if field.is_required() => "*required*".to_string(),
+            None => String::new(),
+        };
+
+        let flag = match field.arg {
+            Some(arg) if flags => code(&format!("--{arg}")),
+            None if flags && !nested => code(&format!("--{path}")),
+            _ => String::new(),
+        };
+
+        rows.push([
+            code(&path),
+            code(field.ty),
+            default,
+            field
+                .env
+                .map(code)
+                .unwrap_or_default(),
+            flag,
+            description(field),
+        ]);
+
+        if nested {
+            match item_type(field.ty) {
+                "Vec" => collect_rows(&field.fields, &format!("{path}[]."), false, rows),
+                _ => collect_rows(&field.fields, &format!("{path}."), flags, rows),
+            }
+        }
+    }
+}
+
+/// Renders the description of a field, listing the values accepted by enums.
+fn description(field: &FieldMetadata) -> String {
+    let mut description = field
+        .description
+        .map(escape)
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    if !field
+        .variants
+        .is_empty()
+    {
+        let variants = field
+            .variants
+            .iter()
+            .map(|variant| code(variant))
+            .collect::<Vec<_>>();
+
+        description.push(format!("One of {}.", variants.join(", ")));
+    }
+
+    description.join(" ")
+}
+
+/// Renders a default value, quoting strings so they can be told apart from
+/// other values.
+fn default_text(default: &DefaultValue) -> String {
+    match default {
+        DefaultValue::Str(s) => format!("{s:?}"),
+        default => default.to_string(),
+    }
+}
+
+/// Returns the name of a type, looking through `Option`, such as `Vec` for
+/// `Option<Vec<String>>`.
+fn item_type(ty: &str) -> &str {
+    match split_type(ty) {
+        ("Option", Some(inner)) => item_type(inner),
+        (name, _) => name,
+    }
+}
+
+/// Formats a table cell as inline code.
+fn code(text: &str) -> String {
+    format!("`{}`", escape(text))
+}
+
+/// Escapes the characters that would break a table row.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace('\n', " ")
+}
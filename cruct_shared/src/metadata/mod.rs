@@ -0,0 +1,192 @@
+use std::fmt::{Display, Formatter, Result as DisplayResult};
+
+use crate::MergeStrategy;
+
+mod example;
+mod json;
+mod markdown;
+mod schema;
+
+#[cfg(test)]
+mod tests;
+
+pub use example::example_config;
+pub use markdown::markdown_reference;
+pub use schema::json_schema;
+
+/// The default value of a field, as declared with `#[field(default = ...)]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+    /// A boolean literal, such as `true`.
+    Bool(bool),
+
+    /// An integer literal, such as `8080` or `-1`.
+    Integer(i128),
+
+    /// A floating point literal, such as `0.5`.
+    Float(f64),
+
+    /// A string or character literal, including string literals converted
+    /// with `to_string` or `into`.
+    Str(&'static str),
+
+    /// Any other expression, as written in the source. Its value is only
+    /// known at runtime.
+    Expr(&'static str),
+}
+
+impl Display for DefaultValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> DisplayResult {
+        match self {
+            DefaultValue::Bool(b) => write!(f, "{b}"),
+            DefaultValue::Integer(i) => write!(f, "{i}"),
+            DefaultValue::Float(x) => write!(f, "{x:?}"),
+            DefaultValue::Str(s) | DefaultValue::Expr(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Describes a field of a `#[cruct]` struct, as returned by the generated
+/// `Struct::field_metadata()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMetadata {
+    /// The name of the field in the struct.
+    pub name: &'static str,
+
+    /// The key the field is looked up with in the configuration.
+    pub key: &'static str,
+
+    /// The type of the field, such as `u16` or `Vec<String>`.
+    pub ty: &'static str,
+
+    /// The default value of the field, if any.
+    pub default: Option<DefaultValue>,
+
+    /// The environment variable overriding the field, if any.
+    pub env: Option<&'static str>,
+
+    /// The command-line flag overriding the field, if any, without the
+    /// leading `--`.
+    pub arg: Option<&'static str>,
+
+    /// The description of the field, from `#[field(description = ...)]` or
+    /// its doc comments.
+    pub description: Option<&'static str>,
+
+    /// Whether the field can be omitted, as with `Option` fields.
+    pub optional: bool,
+
+    /// How the array held by the field is merged across sources, if set.
+    pub merge: Option<MergeStrategy>,
+
+    /// The fields of the nested struct held by this field, directly or as
+    /// the item of an `Option` or `Vec`. Empty for other types.
+    pub fields: Vec<FieldMetadata>,
+
+    /// The values accepted by the enum held by this field, when it is made
+    /// of unit variants only. Empty for other types.
+    pub variants: Vec<&'static str>,
+
+    /// How the newtype struct or data-holding enum held by this field is
+    /// written, directly or as the item of an `Option` or `Vec`. `None` for
+    /// other types.
+    pub type_metadata: Option<TypeMetadata>,
+}
+
+impl FieldMetadata {
+    /// Whether the field must be set by some source, having neither a
+    /// default value nor being optional.
+    ///
+    /// Sections whose fields are all defaulted or optional are not required
+    /// either, as they are read from an empty section when omitted. Arrays
+    /// of sections are still required.
+    pub fn is_required(&self) -> bool {
+        if self.optional
+            || self
+                .default
+                .is_some()
+        {
+            return false;
+        }
+
+        self.fields
+            .is_empty()
+            || split_type(self.ty).0 == "Vec"
+            || self
+                .fields
+                .iter()
+                .any(FieldMetadata::is_required)
+    }
+}
+
+/// Describes how a type is written when its name and fields do not tell,
+/// as returned by the generated `FromConfigValue::type_metadata()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeMetadata {
+    /// A tuple struct holding a single value, written as that value.
+    Newtype {
+        /// The type of the value, such as `u16`.
+        ty: &'static str,
+
+        /// How the type of the value is written, if it is a newtype struct
+        /// or a data-holding enum itself.
+        inner: Option<Box<TypeMetadata>>,
+    },
+
+    /// A tuple struct holding several values, written as an array holding
+    /// one item per value. The values are described as fields keyed by
+    /// their index.
+    Tuple(Vec<FieldMetadata>),
+
+    /// An enum holding data in some variant, or naming its variant by a tag.
+    Enum {
+        /// The key naming the variant of an internally tagged enum, such as
+        /// `type`. `None` for externally tagged enums.
+        tag: Option<&'static str>,
+
+        /// The variants of the enum.
+        variants: Vec<VariantMetadata>,
+    },
+}
+
+/// Describes a variant of an enum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantMetadata {
+    /// The name the variant is written as.
+    pub name: &'static str,
+
+    /// The data held by the variant.
+    pub content: VariantContent,
+}
+
+/// The data held by a variant of an enum.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantContent {
+    /// No data, as in `Memory`.
+    Unit,
+
+    /// A single value, described as a field keyed by the variant name.
+    Newtype(Box<FieldMetadata>),
+
+    /// Several values, described as fields keyed by their index.
+    Tuple(Vec<FieldMetadata>),
+
+    /// Named fields.
+    Struct(Vec<FieldMetadata>),
+}
+
+/// Splits a type into its name, without the module path, and its generic
+/// arguments, such as `Vec` and `String` for `std::vec::Vec<String>`.
+pub(crate) fn split_type(ty: &str) -> (&str, Option<&str>) {
+    let (path, inner) = match ty.split_once('<') {
+        Some((path, rest)) => (path, rest.strip_suffix('>')),
+        None => (ty, None),
+    };
+
+    let name = path
+        .rsplit("::")
+        .next()
+        .unwrap_or(path);
+
+    (name, inner)
+}
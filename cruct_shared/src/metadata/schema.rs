@@ -0,0 +1,316 @@
+use super::json::Json;
+use super::{
+    DefaultValue,
+    FieldMetadata,
+    TypeMetadata,
+    VariantContent,
+    VariantMetadata,
+    split_type,
+};
+
+/// The dialect of the generated schemas.
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Renders a JSON Schema (draft 2020-12) describing a configuration, as
+/// returned by the generated `Struct::json_schema()`.
+///
+/// Each field is described by its key, with a type derived from its Rust
+/// type, its description and its default value. Fields that are neither
+/// optional nor defaulted are required, as are sections holding such a
+/// field. Nested structs are described in `$defs` and referenced by name,
+/// newtypes as the value they wrap and enums holding data as `oneOf` their
+/// variants.
+///
+/// * `title`: The title of the schema, usually the name of the struct.
+/// * `fields`: The fields of the configuration.
+pub fn json_schema(title: &str, fields: &[FieldMetadata]) -> String {
+    let mut defs = Vec::new();
+    let mut schema =
+        Json::object([("$schema", Json::string(DRAFT)), ("title", Json::string(title))]);
+
+    if let Json::Object(members) = object_schema(fields, &mut defs) {
+        for (key, value) in members {
+            schema.insert(&key, value);
+        }
+    }
+
+    if !defs.is_empty() {
+        schema.insert("$defs", Json::Object(defs));
+    }
+
+    format!("{schema}\n")
+}
+
+/// Describes an object holding the given fields.
+///
+/// * `defs`: The definitions of the nested structs found so far.
+fn object_schema(fields: &[FieldMetadata], defs: &mut Vec<(String, Json)>) -> Json {
+    let properties = fields
+        .iter()
+        .map(|field| {
+            (
+                field
+                    .key
+                    .to_string(),
+                field_schema(field, defs),
+            )
+        })
+        .collect();
+
+    let required = fields
+        .iter()
+        .filter(|field| field.is_required())
+        .map(|field| Json::string(field.key))
+        .collect::<Vec<_>>();
+
+    let mut schema =
+        Json::object([("type", Json::string("object")), ("properties", Json::Object(properties))]);
+
+    if !required.is_empty() {
+        schema.insert("required", Json::Array(required));
+    }
+
+    schema
+}
+
+/// Describes a field, along with its description and default value.
+fn field_schema(field: &FieldMetadata, defs: &mut Vec<(String, Json)>) -> Json {
+    let mut schema = type_schema(
+        field.ty,
+        field
+            .type_metadata
+            .as_ref(),
+        field,
+        defs,
+    );
+
+    if let Some(description) = field.description {
+        schema.insert("description", Json::string(description));
+    }
+
+    if let Some(default) = field
+        .default
+        .as_ref()
+        .and_then(default_json)
+    {
+        schema.insert("default", default);
+    }
+
+    schema
+}
+
+/// Describes the values of a Rust type, such as `u16` or `Vec<String>`.
+///
+/// Structs are added to `defs` and referenced. Types that cannot be
+/// described accept any value.
+///
+/// * `metadata`: How the type is written, if it is a newtype struct or an enum
+///   holding data.
+/// * `field`: The field holding the type, whose nested fields and variants
+///   describe it.
+fn type_schema(
+    ty: &str,
+    metadata: Option<&TypeMetadata>,
+    field: &FieldMetadata,
+    defs: &mut Vec<(String, Json)>,
+) -> Json {
+    let (name, inner) = split_type(ty);
+
+    match (name, inner) {
+        ("Option", Some(inner)) => type_schema(inner, metadata, field, defs),
+        ("Vec", Some(inner)) => Json::object([
+            ("type", Json::string("array")),
+            ("items", type_schema(inner, metadata, field, defs)),
+        ]),
+        _ => match metadata {
+            Some(TypeMetadata::Newtype { ty, inner }) => {
+                type_schema(ty, inner.as_deref(), field, defs)
+            },
+            Some(TypeMetadata::Tuple(items)) => tuple_schema(items, defs),
+            Some(TypeMetadata::Enum { tag, variants }) => Json::object([(
+                "oneOf",
+                Json::Array(
+                    variants
+                        .iter()
+                        .flat_map(|variant| variant_schemas(*tag, variant, defs))
+                        .collect(),
+                ),
+            )]),
+            None => named_type_schema(ty, field, defs),
+        },
+    }
+}
+
+/// Describes a type by its name, such as `u16`, or by the fields or unit
+/// variants of the field holding it.
+fn named_type_schema(ty: &str, field: &FieldMetadata, defs: &mut Vec<(String, Json)>) -> Json {
+    let (name, inner) = split_type(ty);
+
+    match (name, inner) {
+        _ if !field
+            .variants
+            .is_empty() =>
+        {
+            Json::object([
+                ("type", Json::string("string")),
+                (
+                    "enum",
+                    Json::Array(
+                        field
+                            .variants
+                            .iter()
+                            .map(|variant| Json::string(*variant))
+                            .collect(),
+                    ),
+                ),
+            ])
+        },
+        _ if !field
+            .fields
+            .is_empty() =>
+        {
+            if !defs
+                .iter()
+                .any(|(key, _)| key == ty)
+            {
+                // Reserved before describing the fields, so that recursive
+                // structs reference their own definition.
+                defs.push((ty.to_string(), Json::Object(Vec::new())));
+                let schema = object_schema(&field.fields, defs);
+
+                if let Some((_, def)) = defs
+                    .iter_mut()
+                    .find(|(key, _)| key == ty)
+                {
+                    *def = schema;
+                }
+            }
+
+            Json::object([("$ref", Json::string(format!("#/$defs/{}", escape_pointer(ty))))])
+        },
+        ("bool", None) => Json::object([("type", Json::string("boolean"))]),
+        ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", None) => {
+            Json::object([("type", Json::string("integer")), ("minimum", Json::Number("0".into()))])
+        },
+        ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", None) => {
+            Json::object([("type", Json::string("integer"))])
+        },
+        ("f32" | "f64", None) => Json::object([("type", Json::string("number"))]),
+        ("char", None) => Json::object([
+            ("type", Json::string("string")),
+            ("minLength", Json::Number("1".into())),
+            ("maxLength", Json::Number("1".into())),
+        ]),
+        ("String" | "PathBuf", None) => Json::object([("type", Json::string("string"))]),
+        _ => Json::Object(Vec::new()),
+    }
+}
+
+/// Describes an array holding one item per value, as tuple structs and
+/// tuple variants are written.
+fn tuple_schema(items: &[FieldMetadata], defs: &mut Vec<(String, Json)>) -> Json {
+    let len = Json::Number(
+        items
+            .len()
+            .to_string(),
+    );
+
+    Json::object([
+        ("type", Json::string("array")),
+        (
+            "prefixItems",
+            Json::Array(
+                items
+                    .iter()
+                    .map(|item| field_schema(item, defs))
+                    .collect(),
+            ),
+        ),
+        ("minItems", len.clone()),
+        ("maxItems", len),
+    ])
+}
+
+/// Describes the ways a variant of an enum can be written.
+///
+/// Variants of externally tagged enums are written as their name when they
+/// hold no data, and as a section holding their data under their name
+/// otherwise. Variants of internally tagged enums are written as a section
+/// whose `tag` key holds their name, next to their fields, and unit
+/// variants as their name alone as well.
+fn variant_schemas(
+    tag: Option<&str>,
+    variant: &VariantMetadata,
+    defs: &mut Vec<(String, Json)>,
+) -> Vec<Json> {
+    let name = variant.name;
+    let constant = || Json::object([("const", Json::string(name))]);
+
+    let Some(tag) = tag else {
+        let content = match &variant.content {
+            VariantContent::Unit => return vec![constant()],
+            VariantContent::Newtype(value) => field_schema(value, defs),
+            VariantContent::Tuple(items) => tuple_schema(items, defs),
+            VariantContent::Struct(fields) => object_schema(fields, defs),
+        };
+
+        return vec![Json::object([
+            ("type", Json::string("object")),
+            ("properties", Json::Object(vec![(name.to_string(), content)])),
+            ("required", Json::Array(vec![Json::string(name)])),
+            ("additionalProperties", Json::Bool(false)),
+        ])];
+    };
+
+    let fields = match &variant.content {
+        VariantContent::Struct(fields) => fields.as_slice(),
+        VariantContent::Newtype(value) => value
+            .fields
+            .as_slice(),
+        VariantContent::Unit | VariantContent::Tuple(_) => &[],
+    };
+
+    let mut schema = object_schema(fields, defs);
+
+    if let Json::Object(members) = &mut schema {
+        for (key, value) in members {
+            match (key.as_str(), value) {
+                ("properties", Json::Object(properties)) => {
+                    properties.insert(0, (tag.to_string(), constant()));
+                },
+                ("required", Json::Array(required)) => required.insert(0, Json::string(tag)),
+                _ => {},
+            }
+        }
+    }
+
+    if !fields
+        .iter()
+        .any(FieldMetadata::is_required)
+    {
+        schema.insert("required", Json::Array(vec![Json::string(tag)]));
+    }
+
+    match variant.content {
+        VariantContent::Unit => vec![constant(), schema],
+        _ => vec![schema],
+    }
+}
+
+/// Escapes a reference token of a JSON Pointer.
+fn escape_pointer(token: &str) -> String {
+    token
+        .replace('~', "~0")
+        .replace('/', "~1")
+}
+
+/// Converts a default value to JSON, if it is known at compile time.
+fn default_json(default: &DefaultValue) -> Option<Json> {
+    match default {
+        DefaultValue::Bool(b) => Some(Json::Bool(*b)),
+        DefaultValue::Integer(i) => Some(Json::Number(i.to_string())),
+        DefaultValue::Float(x) if x.is_finite() => Some(Json::Number(format!("{x:?}"))),
+        DefaultValue::Str(s) => Some(Json::string(*s)),
+        DefaultValue::Float(_) | DefaultValue::Expr(_) => None,
+    }
+}
@@ -0,0 +1,3 @@
+mod test_example;
+mod test_markdown;
+mod test_schema;
@@ -0,0 +1,133 @@
+use crate::{DefaultValue, FieldMetadata, FileFormat, example_config};
+
+fn field(key: &'static str, ty: &'static str) -> FieldMetadata {
+    FieldMetadata {
+        name: key,
+        key,
+        ty,
+        default: None,
+        env: None,
+        arg: None,
+        description: None,
+        optional: false,
+        merge: None,
+        fields: Vec::new(),
+        variants: Vec::new(),
+        type_metadata: None,
+    }
+}
+
+fn fields() -> Vec<FieldMetadata> {
+    let upstream = vec![
+        field("host", "String"),
+        FieldMetadata {
+            default: Some(DefaultValue::Bool(true)),
+            ..field("tls", "bool")
+        },
+    ];
+
+    vec![
+        FieldMetadata {
+            default: Some(DefaultValue::Integer(8080)),
+            description: Some("The port to listen on."),
+            env: Some("APP_PORT"),
+            ..field("port", "u16")
+        },
+        FieldMetadata {
+            optional: true,
+            ..field("motd", "Option<String>")
+        },
+        FieldMetadata {
+            default: Some(DefaultValue::Expr("Vec::new()")),
+            ..field("tags", "Vec<String>")
+        },
+        FieldMetadata {
+            description: Some("The database."),
+            fields: vec![
+                field("url", "String"),
+                FieldMetadata {
+                    default: Some(DefaultValue::Float(0.5)),
+                    ..field("ratio", "f64")
+                },
+            ],
+            ..field("database", "Database")
+        },
+        FieldMetadata {
+            fields: upstream,
+            ..field("upstreams", "Vec<Upstream>")
+        },
+    ]
+}
+
+#[test]
+fn renders_toml() {
+    let expected = r#"# The port to listen on. [env: APP_PORT]
+port = 8080
+# motd = "<String>"
+
+# [default: Vec::new()]
+# tags = ["<String>"]
+
+# The database.
+[database]
+url = "<String>"
+ratio = 0.5
+
+[[upstreams]]
+host = "<String>"
+tls = true
+"#;
+
+    assert_eq!(example_config(&fields(), FileFormat::Toml), expected);
+}
+
+#[test]
+fn renders_yaml() {
+    let expected = r#"# The port to listen on. [env: APP_PORT]
+port: 8080
+# motd: "<String>"
+# [default: Vec::new()]
+# tags: ["<String>"]
+# The database.
+database:
+  url: "<String>"
+  ratio: 0.5
+upstreams:
+  - host: "<String>"
+    tls: true
+"#;
+
+    assert_eq!(example_config(&fields(), FileFormat::Yml), expected);
+}
+
+#[test]
+fn renders_json_without_commented_keys() {
+    let expected = r#"{
+  "port": 8080,
+  "database": {
+    "url": "<String>",
+    "ratio": 0.5
+  },
+  "upstreams": [
+    {
+      "host": "<String>",
+      "tls": true
+    }
+  ]
+}
+"#;
+
+    assert_eq!(example_config(&fields(), FileFormat::Json), expected);
+}
+
+#[test]
+fn optional_tables_are_commented_out() {
+    let fields = [FieldMetadata {
+        optional: true,
+        fields: vec![field("url", "String")],
+        ..field("replica", "Option<Database>")
+    }];
+
+    assert_eq!(example_config(&fields, FileFormat::Toml), "# [replica]\n# url = \"<String>\"\n");
+    assert_eq!(example_config(&fields, FileFormat::Yml), "# replica:\n  # url: \"<String>\"\n");
+}
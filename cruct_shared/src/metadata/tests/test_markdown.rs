@@ -0,0 +1,58 @@
+use crate::{DefaultValue, FieldMetadata, markdown_reference};
+
+fn field(key: &'static str, ty: &'static str) -> FieldMetadata {
+    FieldMetadata {
+        name: key,
+        key,
+        ty,
+        default: None,
+        env: None,
+        arg: None,
+        description: None,
+        optional: false,
+        merge: None,
+        fields: Vec::new(),
+        variants: Vec::new(),
+        type_metadata: None,
+    }
+}
+
+#[test]
+fn renders_a_row_per_key() {
+    let fields = [
+        FieldMetadata {
+            default: Some(DefaultValue::Integer(8080)),
+            description: Some("The port, or 0 | unset."),
+            env: Some("APP_PORT"),
+            arg: Some("port"),
+            ..field("http_port", "u16")
+        },
+        FieldMetadata {
+            optional: true,
+            ..field("motd", "Option<String>")
+        },
+        FieldMetadata {
+            fields: vec![FieldMetadata {
+                default: Some(DefaultValue::Str("localhost")),
+                ..field("host", "String")
+            }],
+            ..field("database", "Database")
+        },
+        FieldMetadata {
+            fields: vec![field("address", "String")],
+            ..field("upstreams", "Vec<Upstream>")
+        },
+    ];
+
+    let expected = r#"| Key | Type | Default | Env | Flag | Description |
+| --- | --- | --- | --- | --- | --- |
+| `http_port` | `u16` | `8080` | `APP_PORT` | `--port` | The port, or 0 \| unset. |
+| `motd` | `Option<String>` |  |  | `--motd` |  |
+| `database` | `Database` |  |  |  |  |
+| `database.host` | `String` | `"localhost"` |  | `--database.host` |  |
+| `upstreams` | `Vec<Upstream>` | *required* |  |  |  |
+| `upstreams[].address` | `String` | *required* |  |  |  |
+"#;
+
+    assert_eq!(markdown_reference(&fields), expected);
+}
@@ -0,0 +1,241 @@
+use crate::{
+    DefaultValue,
+    FieldMetadata,
+    TypeMetadata,
+    VariantContent,
+    VariantMetadata,
+    json_schema,
+};
+
+fn field(key: &'static str, ty: &'static str) -> FieldMetadata {
+    FieldMetadata {
+        name: key,
+        key,
+        ty,
+        default: None,
+        env: None,
+        arg: None,
+        description: None,
+        optional: false,
+        merge: None,
+        fields: Vec::new(),
+        variants: Vec::new(),
+        type_metadata: None,
+    }
+}
+
+#[test]
+fn describes_scalar_fields() {
+    let fields = [
+        FieldMetadata {
+            default: Some(DefaultValue::Integer(8080)),
+            description: Some("The \"main\" port."),
+            ..field("port", "u16")
+        },
+        field("name", "String"),
+        FieldMetadata {
+            optional: true,
+            ..field("ratio", "Option<f64>")
+        },
+    ];
+
+    let expected = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "App",
+  "type": "object",
+  "properties": {
+    "port": {
+      "type": "integer",
+      "minimum": 0,
+      "description": "The \"main\" port.",
+      "default": 8080
+    },
+    "name": {
+      "type": "string"
+    },
+    "ratio": {
+      "type": "number"
+    }
+  },
+  "required": [
+    "name"
+  ]
+}
+"##;
+
+    assert_eq!(json_schema("App", &fields), expected);
+}
+
+#[test]
+fn describes_arrays_of_nested_structs_once() {
+    let server = || FieldMetadata {
+        fields: vec![
+            FieldMetadata {
+                default: Some(DefaultValue::Bool(true)),
+                ..field("tls", "bool")
+            },
+            field("host", "String"),
+        ],
+        ..field("primary", "Server")
+    };
+
+    let fields = [
+        server(),
+        FieldMetadata {
+            key: "replicas",
+            ty: "Vec<Server>",
+            ..server()
+        },
+    ];
+
+    let schema = json_schema("App", &fields);
+
+    assert!(schema.contains(
+        r##""primary": {
+      "$ref": "#/$defs/Server"
+    }"##
+    ));
+    assert!(schema.contains(
+        r##""replicas": {
+      "type": "array",
+      "items": {
+        "$ref": "#/$defs/Server"
+      }
+    }"##
+    ));
+    assert!(schema.contains(
+        r#""$defs": {
+    "Server": {
+      "type": "object",
+      "properties": {
+        "tls": {
+          "type": "boolean",
+          "default": true
+        },"#
+    ));
+    assert_eq!(
+        schema
+            .matches("\"Server\": {")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn unknown_types_and_runtime_defaults_are_left_open() {
+    let fields = [FieldMetadata {
+        default: Some(DefaultValue::Expr("Duration::from_secs(5)")),
+        ..field("timeout", "std::time::Duration")
+    }];
+
+    let schema = json_schema("App", &fields);
+
+    assert!(schema.contains("\"timeout\": {}"));
+    assert!(!schema.contains("required"));
+}
+
+#[test]
+fn describes_newtypes_as_the_value_they_wrap() {
+    let fields = [FieldMetadata {
+        type_metadata: Some(TypeMetadata::Newtype {
+            ty: "Port",
+            inner: Some(Box::new(TypeMetadata::Newtype { ty: "Vec<u16>", inner: None })),
+        }),
+        ..field("ports", "Option<Ports>")
+    }];
+
+    let schema = json_schema("App", &fields);
+
+    assert!(schema.contains(
+        r#""ports": {
+      "type": "array",
+      "items": {
+        "type": "integer",
+        "minimum": 0
+      }
+    }"#
+    ));
+}
+
+#[test]
+fn describes_internally_tagged_variants_by_their_tag() {
+    let variant = |name, content| VariantMetadata { name, content };
+    let fields = [FieldMetadata {
+        type_metadata: Some(TypeMetadata::Enum {
+            tag: Some("type"),
+            variants: vec![
+                variant("s3", VariantContent::Struct(vec![field("bucket", "String")])),
+                variant("memory", VariantContent::Unit),
+            ],
+        }),
+        ..field("storage", "Storage")
+    }];
+
+    let expected = r#""storage": {
+      "oneOf": [
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "s3"
+            },
+            "bucket": {
+              "type": "string"
+            }
+          },
+          "required": [
+            "type",
+            "bucket"
+          ]
+        },
+        {
+          "const": "memory"
+        },
+        {
+          "type": "object",
+          "properties": {
+            "type": {
+              "const": "memory"
+            }
+          },
+          "required": [
+            "type"
+          ]
+        }
+      ]
+    }"#;
+
+    assert!(json_schema("App", &fields).contains(expected));
+}
+
+#[test]
+fn sections_of_defaulted_fields_are_not_required() {
+    let logging = || FieldMetadata {
+        fields: vec![
+            FieldMetadata {
+                default: Some(DefaultValue::Bool(false)),
+                ..field("verbose", "bool")
+            },
+            FieldMetadata {
+                optional: true,
+                ..field("level", "Option<String>")
+            },
+        ],
+        ..field("logging", "Logging")
+    };
+
+    let fields = [
+        logging(),
+        FieldMetadata {
+            key: "sinks",
+            ty: "Vec<Logging>",
+            ..logging()
+        },
+    ];
+
+    assert!(json_schema("App", &fields).contains(
+        r#""required": [
+    "sinks"
+  ]"#
+    ));
+}
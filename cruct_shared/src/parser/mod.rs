@@ -6,6 +6,9 @@ use std::sync::Arc;
 
 use thiserror::Error as ThisError;
 
+use crate::metadata::{FieldMetadata, TypeMetadata};
+use crate::source::{CliFlag, LoadContext, MergeStrategy};
+
 #[cfg(feature = "json")]
 mod json;
 
@@ -36,11 +39,45 @@ pub enum ParserError {
     #[error("'{0}' is not a valid file format")]
     InvalidFileFormat(String),
 
+    /// Indicates that the provided array merge strategy is unsupported.
+    /// Valid strategies are `replace`, `append`, `prepend` and `by_key(name)`.
+    #[error("'{0}' is not a valid merge strategy")]
+    InvalidMergeStrategy(String),
+
     /// Triggered when a required field is missing in the configuration file.
     /// This happens when an expected field is absent.
     #[error("Missing required field: {0}")]
     MissingField(String),
 
+    /// Triggered when the section holding a nested struct is missing.
+    /// Nested structs are only read from their parent section when the field
+    /// is flattened with `#[field(flatten)]`.
+    #[error("Missing required section: {0}")]
+    MissingSection(String),
+
+    /// Raised when a field fails one of its validation parameters, such as
+    /// `range` or `pattern`. Contains the dotted path of the field.
+    #[error("Invalid value for '{field}': {message}")]
+    Validation { field: String, message: String },
+
+    /// Raised when the validation function of a struct annotated with
+    /// `#[cruct(validate = Self::check)]` fails, such as when two of its fields
+    /// contradict each other. Contains the dotted path of the struct, which is
+    /// empty for the root one.
+    #[error("Invalid {}: {message}", describe_section(.path))]
+    InvalidStruct { path: String, message: String },
+
+    /// Raised when keys that no field reads are set in a struct annotated
+    /// with `#[cruct(deny_unknown_fields)]`, such as misspelled keys.
+    /// Lists the dotted path of every such key, along with a suggestion.
+    #[error("Unknown keys: {}", .0.join(", "))]
+    UnknownKeys(Vec<String>),
+
+    /// Raised when a key of a flattened struct is also used by its parent, or
+    /// by another flattened struct, which would make both read the same value.
+    #[error("Key '{key}' of flattened field '{field}' collides with another field")]
+    KeyCollision { field: String, key: String },
+
     /// Occurs when there is a type mismatch in a field within the configuration
     /// file. This happens when a field's value type does not match the
     /// expected type.
@@ -51,6 +88,15 @@ pub enum ParserError {
         found: String,
     },
 
+    /// Raised when a value does not name any variant of an enum.
+    /// Lists the names the variants of the enum are written with.
+    #[error("Unknown variant '{found}' for '{name}', expected one of: {}", .expected.join(", "))]
+    UnknownVariant {
+        name: String,
+        found: String,
+        expected: Vec<String>,
+    },
+
     /// Raised when a file path lacks an extension.
     /// Without an extension, determining the file format becomes impossible.
     #[error("This file has no file extension")]
@@ -65,6 +111,53 @@ pub enum ParserError {
         source: Box<ParserError>,
     },
 
+    /// Raised when configuration files include each other in a loop.
+    /// Contains the chain of files that forms the cycle.
+    #[error("Include cycle detected: {0}")]
+    IncludeCycle(String),
+
+    /// Raised when an include directive cannot be resolved.
+    /// Happens when the directive is not a path or a list of paths, or when
+    /// one of its glob patterns is invalid.
+    #[error("Invalid include directive in '{file}': {reason}")]
+    InvalidInclude { file: String, reason: String },
+
+    /// Raised when placeholders inside configuration values reference
+    /// variables or keys that are not set. Lists every unresolved placeholder
+    /// along with the chain of keys that led to it.
+    #[error("Unresolved variables: {}", .0.join(", "))]
+    UnresolvedVariables(Vec<String>),
+
+    /// Raised when configuration values reference each other in a loop.
+    /// Contains the chain of keys that forms the cycle.
+    #[error("Reference cycle detected: {0}")]
+    ReferenceCycle(String),
+
+    /// Raised when a patch document is malformed, such as a JSON Patch
+    /// operation missing its `op` or `path` member.
+    #[error("Invalid patch document '{file}': {reason}")]
+    InvalidPatch { file: String, reason: String },
+
+    /// Raised when a JSON Patch operation cannot be applied.
+    /// Happens when its path does not exist or a `test` operation fails.
+    #[error("Patch operation '{op}' failed at '{path}': {reason}")]
+    PatchFailed { op: String, path: String, reason: String },
+
+    /// Raised when `--help` is given on the command line.
+    /// Contains the help text, which is meant to be printed before exiting.
+    #[error("{0}")]
+    HelpRequested(String),
+
+    /// Raised when a command-line flag is not part of the CLI definition.
+    #[error("Unknown command-line flag '--{0}'")]
+    UnknownFlag(String),
+
+    /// Raised when a command-line argument cannot be parsed.
+    /// Happens when a flag is missing its value, is given more than once or
+    /// when an argument is not a flag.
+    #[error("Malformed command-line argument '{flag}': {reason}")]
+    MalformedFlag { flag: String, reason: String },
+
     /// Reflects standard IO errors encountered during file operations.
     /// Arises when issues occur while reading or writing files.
     #[error("{0:#}")]
@@ -89,6 +182,26 @@ pub enum ParserError {
     YmlError(#[from] yaml_rust2::ScanError),
 }
 
+impl ParserError {
+    /// Reports this error, raised while reading the section at `section`,
+    /// as a `NestedError` of that section.
+    ///
+    /// Errors already naming the dotted path of the key or section they are
+    /// about, such as `Validation`, are returned as is.
+    pub fn in_section(self, section: impl Into<String>) -> ParserError {
+        match self {
+            e @ (ParserError::Validation { .. }
+            | ParserError::InvalidStruct { .. }
+            | ParserError::KeyCollision { .. }
+            | ParserError::UnknownKeys(_)) => e,
+            e => ParserError::NestedError {
+                section: section.into(),
+                source: Box::new(e),
+            },
+        }
+    }
+}
+
 /// Represents the supported file formats for configuration parsing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FileFormat {
@@ -243,6 +356,118 @@ pub trait FromConfigValue {
     fn from_config_value(value: &ConfigValue) -> Result<Self, ParserError>
     where
         Self: Sized;
+
+    /// Converts a `ConfigValue`, resolving field overrides through the given
+    /// context instead of the process environment and arguments.
+    ///
+    /// Only structs annotated with `#[cruct]` have overrides, other types
+    /// convert the value as `from_config_value` does.
+    fn from_config_value_with(value: &ConfigValue, ctx: &LoadContext) -> Result<Self, ParserError>
+    where
+        Self: Sized,
+    {
+        let _ = ctx;
+        Self::from_config_value(value)
+    }
+
+    /// Converts the keys of a section, removing the ones read so that the
+    /// remaining keys can be reported as unknown. Flattened fields are read
+    /// this way, from the section of their parent.
+    ///
+    /// Only structs annotated with `#[cruct]` remove keys, other types
+    /// convert the whole section as `from_config_value_with` does.
+    fn from_config_section(
+        map: &mut HashMap<String, ConfigValue>,
+        ctx: &LoadContext,
+    ) -> Result<Self, ParserError>
+    where
+        Self: Sized,
+    {
+        Self::from_config_value_with(&ConfigValue::Section(map.clone()), ctx)
+    }
+
+    /// Returns the value an override given as a single string, such as an
+    /// environment variable or a command-line flag, is converted from.
+    ///
+    /// `Vec<T>` reads it as an array holding a single item, other types read
+    /// it as is. `Option<T>` returns the value of `T`.
+    fn override_value(value: String) -> ConfigValue
+    where
+        Self: Sized,
+    {
+        ConfigValue::Value(value)
+    }
+
+    /// Returns the array merge strategies declared by this type, keyed by the
+    /// dotted path of each array relative to this type.
+    ///
+    /// Only structs annotated with `#[cruct]` declare strategies, other types
+    /// return none.
+    fn merge_strategies() -> Vec<(String, MergeStrategy)>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Returns whether keys no field reads are an error for the section this
+    /// type is read from, rather than warnings.
+    ///
+    /// Structs annotated with `#[cruct(deny_unknown_fields)]` and structs
+    /// flattening one of them deny unknown keys, other types do not.
+    fn denies_unknown_fields() -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
+
+    /// Returns the command-line flags overriding the fields of this type,
+    /// keyed relative to this type.
+    ///
+    /// Only structs annotated with `#[cruct]` declare flags, other types
+    /// return none.
+    fn cli_flags() -> Vec<CliFlag>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Returns the metadata of the fields of this type.
+    ///
+    /// Only structs annotated with `#[cruct]` have fields, other types return
+    /// none. `Option<T>` and `Vec<T>` return the fields of `T`.
+    fn field_metadata() -> Vec<FieldMetadata>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Returns the values accepted by an enum made of unit variants only,
+    /// such as `primary` and `replica`.
+    ///
+    /// Other types return none. `Option<T>` and `Vec<T>` return the values
+    /// of `T`.
+    fn variants() -> Vec<&'static str>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Returns how this type is written, when its name and fields do not
+    /// tell, such as for newtype structs and enums holding data.
+    ///
+    /// Only types annotated with `#[cruct]` describe themselves, other types
+    /// return none. `Option<T>` and `Vec<T>` return the metadata of `T`.
+    fn type_metadata() -> Option<TypeMetadata>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Macro to implement FromConfigValue for scalar types.
@@ -266,6 +491,14 @@ macro_rules! impl_from_config_value {
     )*};
 }
 
+/// Describes the section at the given path, for error messages.
+fn describe_section(path: &str) -> String {
+    match path.is_empty() {
+        true => "configuration".to_string(),
+        false => format!("section '{path}'"),
+    }
+}
+
 /// Helper function to parse a string into a specific type.
 /// Returns a ParserError if parsing fails.
 fn parse_value<T: FromStr>(s: &str) -> Result<T, ParserError> {
@@ -288,12 +521,18 @@ where
     T: FromConfigValue,
 {
     fn from_config_value(value: &ConfigValue) -> Result<Self, ParserError> {
+        Self::from_config_value_with(value, &LoadContext::from_process())
+    }
+
+    fn from_config_value_with(value: &ConfigValue, ctx: &LoadContext) -> Result<Self, ParserError> {
         match value {
             ConfigValue::Array(items) => items
                 .iter()
                 .enumerate()
                 .map(|(i, item)| {
-                    T::from_config_value(item).map_err(|_e| ParserError::TypeMismatch {
+                    let ctx = ctx.nested(&i.to_string());
+
+                    T::from_config_value_with(item, &ctx).map_err(|_e| ParserError::TypeMismatch {
                         field: format!("Array item at index {}", i),
                         expected: std::any::type_name::<T>().to_string(),
                         found: item.to_string(),
@@ -307,6 +546,26 @@ where
             }),
         }
     }
+
+    fn override_value(value: String) -> ConfigValue {
+        ConfigValue::Array(vec![ConfigValue::Value(value)])
+    }
+
+    fn merge_strategies() -> Vec<(String, MergeStrategy)> {
+        T::merge_strategies()
+    }
+
+    fn field_metadata() -> Vec<FieldMetadata> {
+        T::field_metadata()
+    }
+
+    fn variants() -> Vec<&'static str> {
+        T::variants()
+    }
+
+    fn type_metadata() -> Option<TypeMetadata> {
+        T::type_metadata()
+    }
 }
 
 /// Helper trait to convert a `ConfigValue` to an `Option<T>`.
@@ -315,13 +574,45 @@ where
     T: FromConfigValue,
 {
     fn from_config_value(value: &ConfigValue) -> Result<Self, ParserError> {
+        Self::from_config_value_with(value, &LoadContext::from_process())
+    }
+
+    fn from_config_value_with(value: &ConfigValue, ctx: &LoadContext) -> Result<Self, ParserError> {
         match value {
             ConfigValue::Null => Ok(None),
-            _ => match T::from_config_value(value) {
+            _ => match T::from_config_value_with(value, ctx) {
                 Ok(v) => Ok(Some(v)),
                 Err(ParserError::MissingField(_)) => Ok(None),
                 Err(e) => Err(e),
             },
         }
     }
+
+    fn override_value(value: String) -> ConfigValue {
+        T::override_value(value)
+    }
+
+    fn merge_strategies() -> Vec<(String, MergeStrategy)> {
+        T::merge_strategies()
+    }
+
+    fn denies_unknown_fields() -> bool {
+        T::denies_unknown_fields()
+    }
+
+    fn cli_flags() -> Vec<CliFlag> {
+        T::cli_flags()
+    }
+
+    fn field_metadata() -> Vec<FieldMetadata> {
+        T::field_metadata()
+    }
+
+    fn variants() -> Vec<&'static str> {
+        T::variants()
+    }
+
+    fn type_metadata() -> Option<TypeMetadata> {
+        T::type_metadata()
+    }
 }
@@ -25,3 +25,26 @@ fn parses_simple_yaml_map() {
         panic!("expected section");
     }
 }
+
+#[test]
+fn unset_tag_is_parsed_as_null() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "foo: !unset\nbar: ~\nbaz: '!unset'").unwrap();
+    let path = file
+        .path()
+        .to_str()
+        .unwrap();
+
+    let src = ConfigFileSource::new(path, Some(FileFormat::Yml));
+    let cfg = src
+        .load()
+        .unwrap();
+
+    if let ConfigValue::Section(map) = cfg {
+        assert_eq!(map["foo"], ConfigValue::Null);
+        assert_eq!(map["bar"], ConfigValue::Null);
+        assert_eq!(map["baz"], ConfigValue::Value("!unset".to_string()));
+    } else {
+        panic!("expected section");
+    }
+}
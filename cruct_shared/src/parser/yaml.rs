@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::fs::read_to_string;
 
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser as EventParser, Tag};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
 use yaml_rust2::{Yaml, YamlLoader};
 
 use super::{ConfigValue, Parser, ParserError};
@@ -15,7 +17,7 @@ impl Parser for YmlParser {
 
     fn load(&self, path: &str) -> Result<ConfigValue, ParserError> {
         let content = read_to_string(path)?;
-        let docs = YamlLoader::load_from_str(&content)?;
+        let docs = load_documents(&content)?;
 
         let doc = docs
             .first()
@@ -29,6 +31,54 @@ impl Parser for YmlParser {
     }
 }
 
+/// The tag used to explicitly unset an inherited key, as in `key: !unset`.
+const UNSET_TAG: &str = "unset";
+
+/// Event receiver that loads YAML documents, turning scalars tagged with
+/// `!unset` into nulls.
+///
+/// `YamlLoader` drops local tags, so the tagged events are rewritten before
+/// reaching it.
+struct UnsetReceiver(YamlLoader);
+
+impl MarkedEventReceiver for UnsetReceiver {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let event = match event {
+            Event::Scalar(_, _, anchor, Some(Tag { handle, suffix }))
+                if handle == "!" && suffix == UNSET_TAG =>
+            {
+                Event::Scalar("~".into(), TScalarStyle::Plain, anchor, None)
+            },
+            event => event,
+        };
+
+        self.0
+            .on_event(event, mark);
+    }
+}
+
+/// Loads every YAML document found in `content`.
+///
+/// Scalars tagged with `!unset` are loaded as `Yaml::Null`, so they remove
+/// the key they are set on when merged over a lower priority source.
+fn load_documents(content: &str) -> Result<Vec<Yaml>, ParserError> {
+    // Surface syntax and duplicate key errors, which are only reported by
+    // `YamlLoader` itself.
+    let docs = YamlLoader::load_from_str(content)?;
+
+    if !content.contains("!unset") {
+        return Ok(docs);
+    }
+
+    let mut receiver = UnsetReceiver(YamlLoader::default());
+    EventParser::new_from_str(content).load(&mut receiver, true)?;
+
+    Ok(receiver
+        .0
+        .documents()
+        .to_vec())
+}
+
 /// Parses a YAML value into a corresponding `ConfigValue` type.
 ///
 /// This function recursively converts YAML structures (e.g., hashes, arrays,
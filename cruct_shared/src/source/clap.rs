@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as DebugResult};
+use std::marker::PhantomData;
+
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Args, Command, Error as ClapError, FromArgMatches};
+
+use super::cli::insert_key;
+use super::{CliDefinition, ConfigSource};
+use crate::{ConfigValue, FromConfigValue, ParserError};
+
+impl CliDefinition {
+    /// Adds an argument for every flag of this definition to a clap command.
+    ///
+    /// Arguments are named after the flags and carry no default value, so
+    /// that values from configuration files are only overridden by flags
+    /// actually given on the command line. Boolean flags are switches and
+    /// arrays accept repeated flags.
+    pub fn augment_args(&self, mut cmd: Command) -> Command {
+        for flag in &self.flags {
+            let mut arg = Arg::new(
+                flag.name()
+                    .to_string(),
+            )
+            .long(
+                flag.name()
+                    .to_string(),
+            )
+            .help(flag.help());
+
+            arg = if flag.is_switch() {
+                arg.action(ArgAction::SetTrue)
+            } else if flag.is_repeatable() {
+                arg.action(ArgAction::Append)
+                    .value_name(
+                        flag.ty
+                            .clone(),
+                    )
+            } else {
+                arg.action(ArgAction::Set)
+                    .value_name(
+                        flag.ty
+                            .clone(),
+                    )
+            };
+
+            cmd = cmd.arg(arg);
+        }
+
+        cmd
+    }
+}
+
+/// A configuration source backed by arguments parsed with clap.
+///
+/// Only the flags of the definition that were given on the command line are
+/// loaded, each one setting the configuration key it is defined for.
+#[derive(Clone, Debug)]
+pub struct ClapSource {
+    value: ConfigValue,
+    priority: u8,
+}
+
+impl ClapSource {
+    /// Creates a new `ClapSource` from the matches of a command augmented
+    /// with [`CliDefinition::augment_args`].
+    ///
+    /// The priority is 0, see [`ClapSource::with_priority`] to change it.
+    pub fn from_matches(matches: &ArgMatches, definition: &CliDefinition) -> Self {
+        let mut map = HashMap::new();
+
+        for flag in &definition.flags {
+            let id = flag.name();
+
+            if matches.value_source(id) != Some(ValueSource::CommandLine) {
+                continue;
+            }
+
+            let values = if flag.is_switch() {
+                vec!["true".to_string()]
+            } else {
+                matches
+                    .get_raw(id)
+                    .into_iter()
+                    .flatten()
+                    .map(|value| {
+                        value
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                    .collect()
+            };
+
+            for value in values {
+                insert_key(&mut map, &flag.key, value, flag.is_repeatable());
+            }
+        }
+
+        ClapSource {
+            value: ConfigValue::Section(map),
+            priority: 0,
+        }
+    }
+
+    /// Sets the priority of this source.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+impl ConfigSource for ClapSource {
+    fn load(&self) -> Result<ConfigValue, ParserError> {
+        Ok(self
+            .value
+            .clone())
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+}
+
+/// The overridable fields of a `#[cruct]` struct, as clap arguments.
+///
+/// This can be flattened into a `clap::Parser` struct, so that the
+/// configuration flags are parsed and listed in `--help` along with the
+/// other arguments of the program:
+///
+/// ```rust ignore
+/// #[derive(clap::Parser)]
+/// struct Cli {
+///     #[command(flatten)]
+///     config: ConfigArgs<Config>,
+/// }
+///
+/// let cli = Cli::parse();
+/// let config = Config::loader()
+///     .with_config()
+///     .with_source(cli.config.source())
+///     .load()?;
+/// ```
+pub struct ConfigArgs<T> {
+    value: ConfigValue,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> ConfigArgs<T>
+where
+    T: FromConfigValue,
+{
+    /// Returns the definition of the flags of `T`.
+    fn definition() -> CliDefinition {
+        CliDefinition::new("", T::cli_flags())
+    }
+
+    /// Returns a source loading the flags given on the command line, with a
+    /// priority of 0.
+    pub fn source(&self) -> ClapSource {
+        ClapSource {
+            value: self
+                .value
+                .clone(),
+            priority: 0,
+        }
+    }
+}
+
+impl<T> Clone for ConfigArgs<T> {
+    fn clone(&self) -> Self {
+        ConfigArgs {
+            value: self
+                .value
+                .clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for ConfigArgs<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> DebugResult {
+        f.debug_struct("ConfigArgs")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> FromArgMatches for ConfigArgs<T>
+where
+    T: FromConfigValue,
+{
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, ClapError> {
+        Ok(ConfigArgs {
+            value: ClapSource::from_matches(matches, &Self::definition()).value,
+            marker: PhantomData,
+        })
+    }
+
+    fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), ClapError> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl<T> Args for ConfigArgs<T>
+where
+    T: FromConfigValue,
+{
+    fn augment_args(cmd: Command) -> Command {
+        Self::definition().augment_args(cmd)
+    }
+
+    fn augment_args_for_update(cmd: Command) -> Command {
+        Self::augment_args(cmd)
+    }
+}
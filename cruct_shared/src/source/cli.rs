@@ -1,50 +1,606 @@
 use std::collections::HashMap;
 use std::env;
 
-use super::ConfigSource;
-use crate::{ConfigValue, ParserError};
+use super::{ConfigSource, join_path};
+use crate::{ConfigValue, FieldMetadata, ParserError};
 
+/// A configuration source backed by the command-line arguments.
+///
+/// The following forms are supported:
+///
+/// * `--key=value` and `--key value`: Sets `key` to `value`.
+/// * `--flag`: Sets `flag` to `true` when not followed by a value.
+/// * `--no-flag`: Sets `flag` to `false`.
+/// * `--tag a --tag b`: Repeated flags collect their values into an array.
+/// * `--database.port=5432`: Dotted keys set values of nested sections.
+///
+/// Arguments that are not flags are ignored, as is everything after a `--`
+/// terminator.
+///
+/// When a [`CliDefinition`] is set, its flags set the key they are defined
+/// for, as do flags named after one of its keys. Boolean flags never take a
+/// separate value, so `--verbose input.txt` leaves `input.txt` as a
+/// positional argument, and repeatable flags collect an array even when given
+/// once. Other flags, such as `--help`, are skipped. Without a definition, a
+/// flag takes the next argument as its value unless it is another flag. See
+/// [`CliSource::with_strict`] to report flags missing from the definition.
 #[derive(Clone)]
 pub struct CliSource {
     priority: u8,
+
+    /// Explicitly supplied arguments, read from the process if not set.
+    args: Option<Vec<String>>,
+
+    /// The flags known to this source, if any.
+    definition: Option<CliDefinition>,
+
+    /// Whether arguments are parsed strictly against the definition.
+    strict: bool,
 }
 
 impl CliSource {
     /// Creates a new `CliSource` with the given priority.
     /// The default priority is 0.
     pub fn new(priority: u8) -> Self {
-        CliSource { priority }
+        CliSource {
+            priority,
+            args: None,
+            definition: None,
+            strict: false,
+        }
+    }
+
+    /// Creates a new `CliSource` parsing the given arguments instead of the
+    /// ones the process was started with.
+    ///
+    /// The arguments must not include the program name. The priority is 0,
+    /// see [`CliSource::with_priority`] to change it.
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        CliSource {
+            priority: 0,
+            args: Some(
+                args.into_iter()
+                    .map(Into::into)
+                    .collect(),
+            ),
+            definition: None,
+            strict: false,
+        }
+    }
+
+    /// Parse the flags of the given definition as defined, setting the key
+    /// they are defined for.
+    pub fn with_definition(mut self, definition: CliDefinition) -> Self {
+        self.definition = Some(definition);
+        self
+    }
+
+    /// Only accept the flags of the definition, reporting `--help`, unknown
+    /// flags and positional arguments as errors, see [`CliDefinition::parse`].
+    ///
+    /// Has no effect unless a definition is set.
+    pub fn with_strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Sets the priority of this source.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
     }
 
     /// Retrieves the command-line arguments.
-    fn get_args() -> Vec<String> {
-        env::args()
-            .skip(1)
-            .collect()
+    fn get_args(&self) -> Vec<String> {
+        match &self.args {
+            Some(args) => args.clone(),
+            None => env::args()
+                .skip(1)
+                .collect(),
+        }
+    }
+}
+
+/// A flag given on the command line, along with its definition if any, which
+/// is either a flag or a key of the [`CliDefinition`].
+pub(crate) struct ParsedFlag<'a> {
+    /// The flag name, without the leading `--`.
+    pub name: String,
+
+    /// The value of the flag, `true` or `false` for switches.
+    pub value: String,
+
+    /// The definition of the flag, if it is known.
+    pub flag: Option<&'a CliFlag>,
+}
+
+impl ParsedFlag<'_> {
+    /// Whether the flag collects an array, even when given once.
+    pub fn is_repeatable(&self) -> bool {
+        self.flag
+            .is_some_and(CliFlag::is_repeatable)
+    }
+}
+
+/// Parses command-line arguments into a configuration tree, keyed by the key
+/// each flag is defined for. Without a definition, flags are keyed by their
+/// name, with one, flags it does not define are skipped.
+///
+/// See [`CliSource`] for the supported forms.
+///
+/// * `args`: The arguments to parse, without the program name.
+/// * `definition`: The known flags, if any.
+pub(crate) fn parse_args<I>(args: I, definition: Option<&CliDefinition>) -> ConfigValue
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut map = HashMap::new();
+
+    for parsed in parse_flags(args, definition) {
+        let repeatable = parsed.is_repeatable();
+        let key = match (parsed.flag, definition) {
+            (Some(flag), _) => flag
+                .key
+                .as_str(),
+            (None, None) => parsed
+                .name
+                .as_str(),
+            (None, Some(_)) => continue,
+        };
+
+        insert_key(&mut map, key, parsed.value, repeatable);
+    }
+
+    ConfigValue::Section(map)
+}
+
+/// Splits command-line arguments into the flags they set, in order.
+///
+/// Flags of the definition take a value unless they are switches, other flags
+/// take the next argument as their value unless it is another flag. Anything
+/// that is not a flag is skipped.
+///
+/// * `args`: The arguments to parse, without the program name.
+/// * `definition`: The known flags, if any.
+pub(crate) fn parse_flags<I>(args: I, definition: Option<&CliDefinition>) -> Vec<ParsedFlag<'_>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let lookup = |name: &str| {
+        definition.and_then(|definition| {
+            definition
+                .flag(name)
+                .or_else(|| definition.key(name))
+        })
+    };
+
+    let mut flags = Vec::new();
+    let mut args = args
+        .into_iter()
+        .peekable();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+
+        let Some(body) = arg.strip_prefix("--") else {
+            continue;
+        };
+
+        let (name, value) = match body.split_once('=') {
+            Some((name, value)) => (name, value.to_string()),
+            None => match (lookup(body), body.strip_prefix("no-")) {
+                (Some(flag), _) if flag.is_switch() => (body, "true".to_string()),
+                (None, Some(name)) => (name, "false".to_string()),
+                _ => {
+                    let value = args
+                        .next_if(|next| !next.starts_with("--"))
+                        .unwrap_or_else(|| "true".to_string());
+                    (body, value)
+                },
+            },
+        };
+
+        if name
+            .split('.')
+            .any(str::is_empty)
+        {
+            continue;
+        }
+
+        flags.push(ParsedFlag {
+            name: name.to_string(),
+            value,
+            flag: lookup(name),
+        });
+    }
+
+    flags
+}
+
+/// Sets the value at the given dotted key, creating the sections leading to
+/// it.
+///
+/// * `repeatable`: Whether the value is collected into an array even when set
+///   once.
+pub(super) fn insert_key(
+    map: &mut HashMap<String, ConfigValue>,
+    key: &str,
+    value: String,
+    repeatable: bool,
+) {
+    let path = key
+        .split('.')
+        .collect::<Vec<_>>();
+
+    insert_path(map, &path, value, repeatable);
+}
+
+/// Sets the value at the given key path, creating the sections leading to it.
+///
+/// Values set more than once are collected into an array, in order, as are
+/// repeatable values set once.
+fn insert_path(
+    map: &mut HashMap<String, ConfigValue>,
+    path: &[&str],
+    value: String,
+    repeatable: bool,
+) {
+    let (key, rest) = path
+        .split_first()
+        .expect("key paths are never empty");
+
+    if rest.is_empty() {
+        insert_value(map, key, value, repeatable);
+        return;
+    }
+
+    let entry = map
+        .entry(key.to_string())
+        .or_insert_with(|| ConfigValue::Section(HashMap::new()));
+
+    if !matches!(entry, ConfigValue::Section(_)) {
+        *entry = ConfigValue::Section(HashMap::new());
+    }
+
+    if let ConfigValue::Section(section) = entry {
+        insert_path(section, rest, value, repeatable);
     }
 }
 
+/// Sets the value at the given key, without splitting it at dots.
+///
+/// Values set more than once are collected into an array, in order, as are
+/// repeatable values set once.
+pub(super) fn insert_value(
+    map: &mut HashMap<String, ConfigValue>,
+    key: &str,
+    value: String,
+    repeatable: bool,
+) {
+    let value = ConfigValue::Value(value);
+    let merged = match map.remove(key) {
+        Some(ConfigValue::Array(mut items)) => {
+            items.push(value);
+            ConfigValue::Array(items)
+        },
+        Some(existing @ ConfigValue::Value(_)) => ConfigValue::Array(vec![existing, value]),
+        _ if repeatable => ConfigValue::Array(vec![value]),
+        _ => value,
+    };
+
+    map.insert(key.to_string(), merged);
+}
+
 impl ConfigSource for CliSource {
     fn load(&self) -> Result<ConfigValue, ParserError> {
-        let mut map: HashMap<String, ConfigValue> = HashMap::new();
-
-        // Use the get_args function to gather CLI args
-        let args = Self::get_args();
-
-        // Parse --key=val flags
-        for arg in args {
-            if let Some(stripped) = arg.strip_prefix("--") {
-                let mut parts = stripped.splitn(2, '=');
-                if let (Some(key), Some(val)) = (parts.next(), parts.next()) {
-                    map.insert(key.to_owned(), ConfigValue::Value(val.to_owned()));
-                }
-            }
+        match &self.definition {
+            Some(definition) if self.strict => definition.parse(self.get_args()),
+            definition => Ok(parse_args(self.get_args(), definition.as_ref())),
         }
-
-        Ok(ConfigValue::Section(map))
     }
 
     fn priority(&self) -> u8 {
         self.priority
     }
 }
+
+/// A command-line flag overriding a configuration key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliFlag {
+    /// The dotted configuration key the flag sets.
+    pub key: String,
+
+    /// The flag name, without the leading `--`, if it differs from the key.
+    pub arg: Option<String>,
+
+    /// The type of the field, such as `u16` or `Vec<String>`.
+    pub ty: String,
+
+    /// The default value of the field, as written in the source.
+    pub default: Option<String>,
+
+    /// The environment variable overriding the field, if any.
+    pub env: Option<String>,
+
+    /// A human readable description of the field.
+    pub description: Option<String>,
+}
+
+impl CliFlag {
+    /// Returns the flag name, without the leading `--`.
+    pub fn name(&self) -> &str {
+        self.arg
+            .as_deref()
+            .unwrap_or(&self.key)
+    }
+
+    /// Whether the flag is a boolean switch, which takes no value and can be
+    /// negated with the `--no-` prefix.
+    pub fn is_switch(&self) -> bool {
+        matches!(
+            self.ty
+                .as_str(),
+            "bool" | "Option<bool>"
+        )
+    }
+
+    /// Whether the flag can be given more than once, collecting an array.
+    pub fn is_repeatable(&self) -> bool {
+        self.ty
+            .starts_with("Vec<")
+            || self
+                .ty
+                .starts_with("Option<Vec<")
+    }
+
+    /// Returns the help text of the flag, made of its description, default
+    /// value and environment variable.
+    pub fn help(&self) -> String {
+        let mut details = self
+            .description
+            .clone()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        if let Some(default) = &self.default {
+            details.push(format!("[default: {default}]"));
+        }
+
+        if let Some(env) = &self.env {
+            details.push(format!("[env: {env}]"));
+        }
+
+        details.join(" ")
+    }
+
+    /// Returns a copy of the flag nested under the given key.
+    ///
+    /// The key is prefixed, as is the flag name unless it was set explicitly.
+    pub fn nested(&self, key: &str) -> Self {
+        CliFlag {
+            key: join_path(key, &self.key),
+            ..self.clone()
+        }
+    }
+}
+
+/// The command-line interface of a configuration, listing every flag that
+/// overrides one of its keys.
+///
+/// Structs annotated with `#[cruct]` generate their definition from the
+/// fields with an `arg_override`, see `Struct::cli_definition()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliDefinition {
+    /// The name of the program, shown in the usage line.
+    pub name: String,
+
+    /// The accepted flags, listed in the `--help` text.
+    pub flags: Vec<CliFlag>,
+
+    /// The keys of the configuration, which flags named after them set when
+    /// parsed leniently, such as `--database.port`.
+    pub keys: Vec<CliFlag>,
+}
+
+impl CliDefinition {
+    /// Creates a definition accepting the given flags.
+    ///
+    /// * `name`: The name of the program, shown in the usage line.
+    /// * `flags`: The accepted flags.
+    pub fn new(name: impl Into<String>, flags: Vec<CliFlag>) -> Self {
+        CliDefinition {
+            name: name.into(),
+            flags,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Accepts the keys of the given fields, and of the fields of their
+    /// nested structs, as flags when parsed leniently.
+    pub fn with_keys(mut self, fields: &[FieldMetadata]) -> Self {
+        self.keys = key_flags(fields, "");
+        self
+    }
+
+    /// Looks up a flag by name.
+    pub fn flag(&self, name: &str) -> Option<&CliFlag> {
+        self.flags
+            .iter()
+            .find(|flag| flag.name() == name)
+    }
+
+    /// Looks up a key by its dotted path.
+    pub fn key(&self, key: &str) -> Option<&CliFlag> {
+        self.keys
+            .iter()
+            .find(|flag| flag.key == key)
+    }
+
+    /// Returns the names of the flags and the keys of this definition.
+    pub(crate) fn names(&self) -> Vec<&str> {
+        self.flags
+            .iter()
+            .map(CliFlag::name)
+            .chain(
+                self.keys
+                    .iter()
+                    .map(|flag| {
+                        flag.key
+                            .as_str()
+                    }),
+            )
+            .collect()
+    }
+
+    /// Renders the `--help` text, listing every flag along with its type,
+    /// description, default value and environment variable.
+    pub fn help(&self) -> String {
+        let mut rows = self
+            .flags
+            .iter()
+            .map(|flag| {
+                let usage = if flag.is_switch() {
+                    format!("    --{}", flag.name())
+                } else {
+                    format!("    --{} <{}>", flag.name(), flag.ty)
+                };
+
+                (usage, flag.help())
+            })
+            .collect::<Vec<_>>();
+
+        rows.push(("-h, --help".to_string(), "Print help".to_string()));
+
+        let width = rows
+            .iter()
+            .map(|(usage, _)| usage.len())
+            .max()
+            .unwrap_or(0);
+
+        let options = rows
+            .iter()
+            .map(|(usage, details)| {
+                format!("  {usage:width$}  {details}")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("Usage: {} [OPTIONS]\n\nOptions:\n{options}\n", self.name)
+    }
+
+    /// Parses command-line arguments against this definition.
+    ///
+    /// The forms described in [`CliSource`] are supported, with flags setting
+    /// the key they are defined for. Boolean flags never take a separate
+    /// value, so `--verbose` is always `true`.
+    ///
+    /// Returns `ParserError::HelpRequested` holding the help text if `--help`
+    /// or `-h` is given, `ParserError::UnknownFlag` for flags that are not
+    /// defined and `ParserError::MalformedFlag` for arguments that cannot be
+    /// parsed, such as a flag missing its value.
+    ///
+    /// * `args`: The arguments to parse, without the program name.
+    pub fn parse<I>(&self, args: I) -> Result<ConfigValue, ParserError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut map = HashMap::new();
+        let mut seen = Vec::new();
+        let mut args = args
+            .into_iter()
+            .peekable();
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                break;
+            }
+
+            if arg == "--help" || arg == "-h" {
+                return Err(ParserError::HelpRequested(self.help()));
+            }
+
+            let malformed =
+                |reason: String| ParserError::MalformedFlag { flag: arg.clone(), reason };
+
+            let Some(body) = arg.strip_prefix("--") else {
+                return Err(malformed("expected a flag starting with `--`".into()));
+            };
+
+            let (name, inline) = match body.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (body, None),
+            };
+
+            let (flag, value) = match self.flag(name) {
+                Some(flag) => (flag, inline),
+                None => match name
+                    .strip_prefix("no-")
+                    .and_then(|name| self.flag(name))
+                    .filter(|flag| flag.is_switch())
+                {
+                    Some(flag) if inline.is_none() => (flag, Some("false".to_string())),
+                    Some(_) => return Err(malformed("negated flags take no value".into())),
+                    None => return Err(ParserError::UnknownFlag(name.to_string())),
+                },
+            };
+
+            let value = match value {
+                Some(value) => value,
+                None if flag.is_switch() => "true".to_string(),
+                None => args
+                    .next_if(|next| !next.starts_with("--"))
+                    .ok_or_else(|| malformed(format!("expected a value of type {}", flag.ty)))?,
+            };
+
+            if seen.contains(&flag.key) && !flag.is_repeatable() {
+                return Err(malformed("given more than once".into()));
+            }
+
+            insert_key(&mut map, &flag.key, value, flag.is_repeatable());
+            seen.push(
+                flag.key
+                    .clone(),
+            );
+        }
+
+        Ok(ConfigValue::Section(map))
+    }
+}
+
+/// Describes the keys of the given fields as flags, nesting the keys of the
+/// fields of nested structs under their section.
+///
+/// * `prefix`: The dotted path of the section holding the fields.
+fn key_flags(fields: &[FieldMetadata], prefix: &str) -> Vec<CliFlag> {
+    fields
+        .iter()
+        .flat_map(|field| {
+            let key = join_path(prefix, field.key);
+
+            match field
+                .fields
+                .is_empty()
+            {
+                true => vec![CliFlag {
+                    key,
+                    arg: None,
+                    ty: field
+                        .ty
+                        .to_string(),
+                    default: None,
+                    env: None,
+                    description: None,
+                }],
+                false => key_flags(&field.fields, &key),
+            }
+        })
+        .collect()
+}
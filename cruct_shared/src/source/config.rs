@@ -1,12 +1,30 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use super::ConfigSource;
+use super::include::load_with_includes;
+use super::{ConfigSource, merge_configs};
 use crate::parser::get_file_extension;
 use crate::{ConfigValue, FileFormat, Parser, ParserError, get_parser};
 
+/// A configuration source backed by a file on disk.
+///
+/// Files may reference other files through an include directive (`include`
+/// in TOML, `$include` in JSON and YAML) holding a path or a list of paths,
+/// which may contain glob patterns. Included files are resolved relative to
+/// the including file and merged beneath its values.
+///
+/// Files marked with `with_profiles` are profile files, made of a `[default]`
+/// table and one table per profile: the table named after the active profile
+/// is merged over the `[default]` one, which is used alone when no profile is
+/// active, and every other table is ignored. When a profile is active, an
+/// overlay file named after it and placed next to the file
+/// (`config.prod.toml` for `config.toml`) is then merged on top, if it
+/// exists.
 pub struct ConfigFileSource {
     path: String,
     format: Option<FileFormat>,
+    profiles: bool,
 }
 
 impl ConfigFileSource {
@@ -16,7 +34,18 @@ impl ConfigFileSource {
     /// * `format`: Optional file format. If not provided, the format will be
     ///   inferred from the file extension.
     pub fn new(path: impl Into<String>, format: Option<FileFormat>) -> Self {
-        ConfigFileSource { path: path.into(), format }
+        ConfigFileSource {
+            path: path.into(),
+            format,
+            profiles: false,
+        }
+    }
+
+    /// Marks the file as a profile file, whose top-level tables are the
+    /// `default` profile and the profiles merged over it.
+    pub fn with_profiles(mut self) -> Self {
+        self.profiles = true;
+        self
     }
 
     /// Retrieves the parser based on the file format or extension.
@@ -32,12 +61,83 @@ impl ConfigFileSource {
 
         get_parser(&ext)
     }
+
+    /// Returns the path of the overlay file for the given profile.
+    ///
+    /// The profile name is inserted before the file extension, so
+    /// `config.toml` becomes `config.prod.toml`.
+    fn overlay_path(&self, profile: &str) -> PathBuf {
+        let path = Path::new(&self.path);
+
+        let stem = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+
+        let name = match path.extension() {
+            Some(ext) => format!("{stem}.{profile}.{}", ext.to_string_lossy()),
+            None => format!("{stem}.{profile}"),
+        };
+
+        path.with_file_name(name)
+    }
+
+    /// Loads the file and its includes, without selecting a profile.
+    fn load_file(&self) -> Result<ConfigValue, ParserError> {
+        let parser = self.get_parser()?;
+        load_with_includes(Path::new(&self.path), parser, &mut Vec::new())
+    }
+
+    /// Loads the file, selecting the given profile if it is a profile file.
+    fn load_selected(&self, profile: &str) -> Result<ConfigValue, ParserError> {
+        let value = self.load_file()?;
+
+        match self.profiles {
+            true => select_profile(value, profile),
+            false => Ok(value),
+        }
+    }
+}
+
+/// Selects the given profile from a profile file.
+///
+/// The `default` table, or an empty section without one, is returned for the
+/// `default` profile and for profiles without a table of their own. Values
+/// that are not sections are returned unchanged.
+///
+/// * `value`: The configuration loaded from the file.
+/// * `profile`: The name of the active profile.
+fn select_profile(value: ConfigValue, profile: &str) -> Result<ConfigValue, ParserError> {
+    match value {
+        ConfigValue::Section(mut map) => {
+            let default = map
+                .remove("default")
+                .unwrap_or(ConfigValue::Section(HashMap::new()));
+
+            match map.remove(profile) {
+                Some(selected) if profile != "default" => merge_configs(default, selected),
+                _ => Ok(default),
+            }
+        },
+        value => Ok(value),
+    }
 }
 
 impl ConfigSource for ConfigFileSource {
     fn load(&self) -> Result<ConfigValue, ParserError> {
-        let parser = self.get_parser()?;
-        parser.load(&self.path)
+        self.load_selected("default")
+    }
+
+    fn load_profile(&self, profile: &str) -> Result<ConfigValue, ParserError> {
+        let value = self.load_selected(profile)?;
+
+        let overlay = self.overlay_path(profile);
+        if !overlay.is_file() {
+            return Ok(value);
+        }
+
+        let overlay = ConfigFileSource::new(overlay.to_string_lossy(), self.format).load_file()?;
+        merge_configs(value, overlay)
     }
 }
 
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{Debug, Formatter, Result as DebugResult};
+use std::sync::{Arc, Mutex};
+
+use super::cli::{insert_value, parse_flags};
+use super::warning::suggest;
+use super::{CliDefinition, ConfigWarning, join_path};
+use crate::ConfigValue;
+
+/// A function looking up environment variables by name.
+pub type EnvProvider = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// The environment and command-line arguments configuration is loaded with.
+///
+/// Field overrides (`env_override` and `arg_override`) are resolved through
+/// the context instead of the process globals, so both can be supplied
+/// explicitly, such as in tests running in parallel. The default context has
+/// no environment variables nor arguments, see [`LoadContext::from_process`]
+/// to read the ones of the process.
+///
+/// The context also tracks the path of the section being loaded and collects
+/// the warnings raised while loading, which are shared by every context
+/// derived from it with [`LoadContext::nested`].
+#[derive(Clone)]
+pub struct LoadContext {
+    env: EnvProvider,
+    args: Arc<Vec<String>>,
+
+    /// The flags set by the arguments, keyed by their full name, such as
+    /// `database.port`. Names are not split at dots, so that a flag never
+    /// reads the value of a longer one.
+    flags: Arc<HashMap<String, ConfigValue>>,
+
+    /// The names of the flags the definition does not know, in order.
+    unknown_flags: Arc<Vec<String>>,
+
+    path: String,
+    warnings: Arc<Mutex<Vec<ConfigWarning>>>,
+}
+
+impl LoadContext {
+    /// Creates a context from an environment provider and a list of
+    /// arguments, without the program name.
+    ///
+    /// The arguments are parsed once, against the given definition if any, so
+    /// that switches never take a separate value and repeatable flags collect
+    /// an array.
+    ///
+    /// * `env`: The function used to look up environment variables.
+    /// * `args`: The command-line arguments.
+    /// * `definition`: The known flags, if any.
+    pub fn new(env: EnvProvider, args: Vec<String>, definition: Option<&CliDefinition>) -> Self {
+        let (flags, unknown_flags) = parse_flag_names(&args, definition);
+
+        LoadContext {
+            flags: Arc::new(flags),
+            unknown_flags: Arc::new(unknown_flags),
+            env,
+            args: Arc::new(args),
+            path: String::new(),
+            warnings: Arc::default(),
+        }
+    }
+
+    /// Creates a context reading the process environment and arguments,
+    /// parsed without a definition.
+    pub fn from_process() -> Self {
+        LoadContext::new(
+            Arc::new(|name| env::var(name).ok()),
+            env::args()
+                .skip(1)
+                .collect(),
+            None,
+        )
+    }
+
+    /// Returns a context for loading the section at `key`, relative to the
+    /// section of this context, sharing its warnings.
+    pub fn nested(&self, key: &str) -> Self {
+        LoadContext { path: self.path_of(key), ..self.clone() }
+    }
+
+    /// Returns the dotted path of the section of this context, which is empty
+    /// for the root one.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the dotted path of `key`, relative to the section of this
+    /// context, such as `database.port`.
+    pub fn path_of(&self, key: &str) -> String {
+        join_path(&self.path, key)
+    }
+
+    /// Records a warning.
+    pub fn warn(&self, warning: ConfigWarning) {
+        self.warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(warning);
+    }
+
+    /// Records an `UnknownKey` warning for each of the given keys of the
+    /// section of this context, in sorted order, suggesting the most similar
+    /// of the keys known to the section.
+    ///
+    /// * `keys`: The keys no field read.
+    /// * `known`: The keys read by the fields of the section.
+    pub fn unknown_keys<'a, I>(&self, keys: I, known: &[&str])
+    where
+        I: IntoIterator<Item = &'a String>,
+    {
+        let mut keys = keys
+            .into_iter()
+            .collect::<Vec<_>>();
+        keys.sort();
+
+        for key in keys {
+            self.warn(ConfigWarning::UnknownKey {
+                key: self.path_of(key),
+                suggestion: suggest(key, known).map(str::to_string),
+            });
+        }
+    }
+
+    /// Takes the `UnknownKey` warnings recorded for the section of this
+    /// context and its nested sections, leaving every other warning.
+    ///
+    /// Returns the descriptions of the keys, sorted by path, such as
+    /// ``'server.htpp_port' (did you mean `http_port`?)``.
+    pub fn take_unknown_keys(&self) -> Vec<String> {
+        let mut warnings = self
+            .warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let (unknown, other) = std::mem::take(&mut *warnings)
+            .into_iter()
+            .partition::<Vec<_>, _>(|warning| match warning {
+                ConfigWarning::UnknownKey { key, .. } => {
+                    self.path
+                        .is_empty()
+                        || key
+                            .strip_prefix(&self.path)
+                            .is_some_and(|rest| rest.starts_with('.'))
+                },
+                _ => false,
+            });
+
+        *warnings = other;
+
+        let mut keys = unknown
+            .into_iter()
+            .filter_map(|warning| match warning {
+                ConfigWarning::UnknownKey { key, suggestion: None } => Some(format!("'{key}'")),
+                ConfigWarning::UnknownKey { key, suggestion: Some(suggestion) } => {
+                    Some(format!("'{key}' (did you mean `{suggestion}`?)"))
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Takes the warnings recorded so far, in the order they were raised.
+    pub fn take_warnings(&self) -> Vec<ConfigWarning> {
+        std::mem::take(
+            &mut *self
+                .warnings
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+
+    /// Looks up an environment variable.
+    pub fn var(&self, name: &str) -> Option<String> {
+        (self.env)(name)
+    }
+
+    /// Returns the environment provider of this context.
+    pub fn env(&self) -> &EnvProvider {
+        &self.env
+    }
+
+    /// Returns the command-line arguments of this context.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Returns the names of the flags given on the command line that the
+    /// definition the context was created with does not know, without the
+    /// leading `--`. Empty without a definition.
+    pub fn unknown_flags(&self) -> &[String] {
+        &self.unknown_flags
+    }
+
+    /// Looks up the value of a command-line flag, such as `--port=8080` for
+    /// `port`.
+    ///
+    /// Flags are parsed once, as described in [`CliSource`](crate::CliSource),
+    /// and looked up by their full name, so dotted flags like
+    /// `database.port` are supported as well, but `--database.port` does not
+    /// set `database`.
+    pub fn arg(&self, flag: &str) -> Option<ConfigValue> {
+        self.flags
+            .get(flag)
+            .cloned()
+    }
+}
+
+/// Parses command-line arguments into values keyed by flag name, along with
+/// the names of the flags the definition does not know, if any.
+fn parse_flag_names(
+    args: &[String],
+    definition: Option<&CliDefinition>,
+) -> (HashMap<String, ConfigValue>, Vec<String>) {
+    let mut map = HashMap::new();
+    let mut unknown = Vec::new();
+
+    for parsed in parse_flags(args.to_vec(), definition) {
+        if definition.is_some()
+            && parsed
+                .flag
+                .is_none()
+        {
+            unknown.push(
+                parsed
+                    .name
+                    .clone(),
+            );
+        }
+
+        let repeatable = parsed.is_repeatable();
+        insert_value(&mut map, &parsed.name, parsed.value, repeatable);
+    }
+
+    (map, unknown)
+}
+
+impl Default for LoadContext {
+    /// Creates a context without environment variables nor arguments.
+    fn default() -> Self {
+        LoadContext::new(Arc::new(|_| None), Vec::new(), None)
+    }
+}
+
+impl Debug for LoadContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> DebugResult {
+        f.debug_struct("LoadContext")
+            .field("args", &self.args)
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use glob::glob;
+
+use super::merge_configs;
+use crate::parser::get_file_extension;
+use crate::{ConfigValue, FileFormat, Parser, ParserError, get_parser};
+
+/// Returns the key holding the include directive for the given file format.
+///
+/// TOML uses a plain `include` key, while JSON and YAML use `$include`, as
+/// those formats have no comment or directive syntax of their own.
+fn include_key(format: FileFormat) -> &'static str {
+    match format {
+        #[cfg(feature = "toml")]
+        FileFormat::Toml => "include",
+
+        #[allow(unreachable_patterns)]
+        _ => "$include",
+    }
+}
+
+/// Loads a configuration file, resolving its include directives.
+///
+/// Included files are resolved relative to the including file and merged in
+/// the order they are listed, after which the including file's own values are
+/// merged on top of them.
+///
+/// * `path`: The path of the file to load.
+/// * `parser`: The parser used to load the file.
+/// * `stack`: The canonical paths of the files currently being loaded, used to
+///   detect include cycles.
+pub(crate) fn load_with_includes(
+    path: &Path,
+    parser: Arc<dyn Parser>,
+    stack: &mut Vec<PathBuf>,
+) -> Result<ConfigValue, ParserError> {
+    let canonical = path.canonicalize()?;
+
+    if let Some(start) = stack
+        .iter()
+        .position(|p| p == &canonical)
+    {
+        let chain = stack[start..]
+            .iter()
+            .chain([&canonical])
+            .map(|p| {
+                p.display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        return Err(ParserError::IncludeCycle(chain));
+    }
+
+    let display_path = path
+        .to_string_lossy()
+        .into_owned();
+
+    let mut value = parser.load(&display_path)?;
+
+    let directive = match &mut value {
+        ConfigValue::Section(map) => map.remove(include_key(parser.format())),
+        _ => None,
+    };
+
+    let Some(directive) = directive else {
+        return Ok(value);
+    };
+
+    let base_dir = path
+        .parent()
+        .unwrap_or(Path::new(""));
+
+    stack.push(canonical);
+
+    let mut included = ConfigValue::Section(HashMap::new());
+    for pattern in include_patterns(directive, &display_path)? {
+        for file in expand_pattern(base_dir, &pattern, &display_path)? {
+            let parser = get_parser(&get_file_extension(&file.to_string_lossy())?)?;
+            let next = load_with_includes(&file, parser, stack)?;
+            included = merge_configs(included, next)?;
+        }
+    }
+
+    stack.pop();
+
+    merge_configs(included, value)
+}
+
+/// Extracts the list of include patterns from a directive value.
+///
+/// * `directive`: The value found under the include key.
+/// * `file`: The including file, used for error reporting.
+fn include_patterns(directive: ConfigValue, file: &str) -> Result<Vec<String>, ParserError> {
+    let invalid = |found: &ConfigValue| ParserError::InvalidInclude {
+        file: file.to_string(),
+        reason: format!("expected a path or a list of paths, found '{found}'"),
+    };
+
+    match directive {
+        ConfigValue::Value(pattern) => Ok(vec![pattern]),
+        ConfigValue::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                ConfigValue::Value(pattern) => Ok(pattern),
+                other => Err(invalid(&other)),
+            })
+            .collect(),
+        other => Err(invalid(&other)),
+    }
+}
+
+/// Expands an include pattern into the files it refers to.
+///
+/// Patterns containing glob metacharacters may match any number of files,
+/// which are returned in alphabetical order. Plain paths are returned as is,
+/// so a missing file surfaces as an IO error when it is loaded.
+///
+/// * `base_dir`: The directory of the including file.
+/// * `pattern`: The pattern as written in the directive.
+/// * `file`: The including file, used for error reporting.
+fn expand_pattern(base_dir: &Path, pattern: &str, file: &str) -> Result<Vec<PathBuf>, ParserError> {
+    let full = base_dir.join(pattern);
+
+    if !pattern.contains(['*', '?', '[']) {
+        return Ok(vec![full]);
+    }
+
+    let invalid = |reason: String| ParserError::InvalidInclude { file: file.to_string(), reason };
+
+    glob(&full.to_string_lossy())
+        .map_err(|e| invalid(format!("invalid pattern '{pattern}': {e}")))?
+        .map(|entry| entry.map_err(|e| invalid(e.to_string())))
+        .collect()
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use super::join_path;
+use crate::{ConfigValue, ParserError};
+
+/// Expands `${NAME}` placeholders found in the string values of a
+/// configuration tree.
+///
+/// A placeholder name is first looked up as a dotted key path in `value`
+/// itself (such as `paths.base`, or `servers.0.host` for array items), so
+/// values can reference each other. References are resolved against the tree
+/// as given, which usually is the fully merged configuration, so overrides
+/// from higher priority sources propagate to every value referencing them.
+/// Names that are not keys of the tree are resolved through `lookup`, which
+/// usually reads the process environment.
+///
+/// The following forms are supported:
+///
+/// * `${NAME}`: Replaced by the value of `NAME`.
+/// * `${NAME:-fallback}`: Replaced by the value of `NAME`, or `fallback` if it
+///   is unset or empty.
+/// * `$${NAME}`: Escaped placeholder, replaced by the literal `${NAME}`.
+///
+/// All unresolved placeholders are collected and reported together in a
+/// single `ParserError::UnresolvedVariables` error, each one along with the
+/// chain of keys that led to it. References forming a loop are reported as a
+/// `ParserError::ReferenceCycle`.
+pub fn interpolate<F>(value: ConfigValue, lookup: F) -> Result<ConfigValue, ParserError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut resolver = Resolver {
+        root: &value,
+        lookup: &lookup,
+        cache: HashMap::new(),
+        chain: Vec::new(),
+        unresolved: Vec::new(),
+    };
+
+    let expanded = resolver.value(&value, "")?;
+
+    if resolver
+        .unresolved
+        .is_empty()
+    {
+        Ok(expanded)
+    } else {
+        Err(ParserError::UnresolvedVariables(resolver.unresolved))
+    }
+}
+
+/// Looks up a dotted key path in a configuration tree.
+///
+/// Numeric segments index into arrays.
+///
+/// * `root`: The tree to search.
+/// * `path`: The dotted path to look up.
+fn get_path<'a>(root: &'a ConfigValue, path: &str) -> Option<&'a ConfigValue> {
+    path.split('.')
+        .try_fold(root, |current, segment| match current {
+            ConfigValue::Section(map) => map.get(segment),
+            ConfigValue::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Holds the state of a single interpolation pass.
+struct Resolver<'a, F> {
+    /// The tree references are resolved against.
+    root: &'a ConfigValue,
+
+    /// The function used to resolve names that are not keys of the tree.
+    lookup: &'a F,
+
+    /// Fully expanded values, by key path.
+    cache: HashMap<String, String>,
+
+    /// The key paths currently being expanded, in reference order.
+    chain: Vec<String>,
+
+    /// The reference chains that ended in an unresolved name.
+    unresolved: Vec<String>,
+}
+
+impl<F> Resolver<'_, F>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    /// Recursively expands the placeholders of every string in `value`.
+    ///
+    /// * `value`: The value to expand.
+    /// * `path`: The key path of `value` inside the tree.
+    fn value(&mut self, value: &ConfigValue, path: &str) -> Result<ConfigValue, ParserError> {
+        Ok(match value {
+            ConfigValue::Value(raw) => ConfigValue::Value(self.key(path, raw)?),
+            ConfigValue::Section(map) => ConfigValue::Section(
+                map.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.value(v, &join_path(path, k))?)))
+                    .collect::<Result<_, ParserError>>()?,
+            ),
+            ConfigValue::Array(items) => ConfigValue::Array(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| self.value(v, &join_path(path, &i.to_string())))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ConfigValue::Null => ConfigValue::Null,
+        })
+    }
+
+    /// Expands the string stored at `path`, tracking it in the reference
+    /// chain.
+    ///
+    /// * `path`: The key path of the string.
+    /// * `raw`: The string as found in the tree.
+    fn key(&mut self, path: &str, raw: &str) -> Result<String, ParserError> {
+        if let Some(expanded) = self
+            .cache
+            .get(path)
+        {
+            return Ok(expanded.clone());
+        }
+
+        if let Some(start) = self
+            .chain
+            .iter()
+            .position(|p| p == path)
+        {
+            let cycle = self.chain[start..]
+                .iter()
+                .map(String::as_str)
+                .chain([path])
+                .collect::<Vec<_>>()
+                .join(" -> ");
+
+            return Err(ParserError::ReferenceCycle(cycle));
+        }
+
+        let unresolved = self
+            .unresolved
+            .len();
+
+        self.chain
+            .push(path.to_string());
+        let expanded = self.expand(raw)?;
+        self.chain
+            .pop();
+
+        if self
+            .unresolved
+            .len()
+            == unresolved
+        {
+            self.cache
+                .insert(path.to_string(), expanded.clone());
+        }
+
+        Ok(expanded)
+    }
+
+    /// Resolves a placeholder name, either as a key of the tree or through
+    /// the lookup function.
+    ///
+    /// * `name`: The name found inside the placeholder.
+    fn resolve(&mut self, name: &str) -> Result<Option<String>, ParserError> {
+        match get_path(self.root, name) {
+            Some(ConfigValue::Value(raw)) => self
+                .key(name, raw)
+                .map(Some),
+            Some(other) => Err(ParserError::TypeMismatch {
+                field: name.to_string(),
+                expected: "scalar value".into(),
+                found: other.to_string(),
+            }),
+            None => Ok((self.lookup)(name)),
+        }
+    }
+
+    /// Expands the placeholders found in a single string.
+    ///
+    /// Text that does not form a complete placeholder, such as a lone `$` or
+    /// an unterminated `${`, is kept as is.
+    fn expand(&mut self, input: &str) -> Result<String, ParserError> {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(pos) = rest.find('$') {
+            output.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+
+            if let Some(escaped) = after.strip_prefix("${") {
+                output.push_str("${");
+                rest = escaped;
+                continue;
+            }
+
+            let placeholder = after
+                .strip_prefix('{')
+                .and_then(|body| {
+                    body.find('}')
+                        .map(|end| (&body[..end], &body[end + 1..]))
+                });
+
+            match placeholder {
+                Some((body, remaining)) => {
+                    let (name, fallback) = match body.split_once(":-") {
+                        Some((name, fallback)) => (name, Some(fallback)),
+                        None => (body, None),
+                    };
+
+                    let resolved = self
+                        .resolve(name)?
+                        .filter(|v| fallback.is_none() || !v.is_empty())
+                        .or_else(|| fallback.map(str::to_string));
+
+                    match resolved {
+                        Some(v) => output.push_str(&v),
+                        None => {
+                            let chain = self
+                                .chain
+                                .iter()
+                                .map(String::as_str)
+                                .chain([name])
+                                .collect::<Vec<_>>()
+                                .join(" -> ");
+
+                            if !self
+                                .unresolved
+                                .contains(&chain)
+                            {
+                                self.unresolved
+                                    .push(chain);
+                            }
+                        },
+                    }
+
+                    rest = remaining;
+                },
+                None => {
+                    output.push('$');
+                    rest = after;
+                },
+            }
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+}
@@ -1,16 +1,34 @@
 use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use warning::suggest;
 
 use crate::{ConfigValue, ParserError};
 
+#[cfg(feature = "clap")]
+mod clap;
 mod cli;
 mod config;
+mod context;
+mod include;
+mod interpolate;
+mod patch;
+mod warning;
 
 #[cfg(test)]
 mod tests;
 
-pub use cli::CliSource;
+#[cfg(feature = "clap")]
+pub use clap::{ClapSource, ConfigArgs};
+pub use cli::{CliDefinition, CliFlag, CliSource};
 pub use config::ConfigFileSource;
+pub use context::{EnvProvider, LoadContext};
+pub use interpolate::interpolate;
+pub use patch::{JsonPatchSource, MergePatchSource, merge_patch};
+pub use warning::ConfigWarning;
 
 /// Trait defining a configuration source.
 ///
@@ -24,6 +42,35 @@ pub trait ConfigSource {
     /// (`ConfigValue`) or a parsing error (`ParserError`) if loading fails.
     fn load(&self) -> Result<ConfigValue, ParserError>;
 
+    /// Load configuration from the source for the given profile.
+    ///
+    /// Sources that do not distinguish between profiles load the same
+    /// configuration for every profile, which is the default behavior.
+    fn load_profile(&self, profile: &str) -> Result<ConfigValue, ParserError> {
+        let _ = profile;
+        self.load()
+    }
+
+    /// Applies this source on top of `base`, the configuration merged from
+    /// every lower priority source.
+    ///
+    /// By default, the configuration loaded for the active profile, if any,
+    /// is merged over `base` using the given array merge strategies. Sources
+    /// such as patches override this to transform `base` directly.
+    fn apply(
+        &self,
+        base: ConfigValue,
+        profile: Option<&str>,
+        strategies: &HashMap<String, MergeStrategy>,
+    ) -> Result<ConfigValue, ParserError> {
+        let next = match profile {
+            Some(profile) => self.load_profile(profile)?,
+            None => self.load()?,
+        };
+
+        merge_configs_with(base, next, strategies)
+    }
+
     /// Defines the priority of this configuration source.
     ///
     /// Sources with higher priority override those with lower priority during
@@ -38,24 +85,95 @@ pub trait ConfigSource {
 /// This function recursively merges two `HashMap` instances containing
 /// `ConfigValue::Section` entries. If both sections contain nested sections,
 /// they are merged recursively. Otherwise, values from `high` override those
-/// in `base`.
+/// in `base`. A `ConfigValue::Null` in `high` unsets the key, removing it from
+/// the merged map.
 pub fn merge_sections(
     mut base: HashMap<String, ConfigValue>,
     high: HashMap<String, ConfigValue>,
 ) -> HashMap<String, ConfigValue> {
     for (k, v_high) in high {
         match (base.remove(&k), v_high) {
+            (_old, ConfigValue::Null) => {},
             (Some(ConfigValue::Section(bsub)), ConfigValue::Section(hsub)) => {
                 base.insert(k, ConfigValue::Section(merge_sections(bsub, hsub)));
             },
             (_old, v_high) => {
-                base.insert(k, v_high);
+                base.insert(k, remove_nulls(v_high));
             },
         }
     }
     base
 }
 
+/// Removes the keys set to `ConfigValue::Null` from every section in `value`.
+///
+/// This applies unset semantics to values that are not merged over anything,
+/// such as sections that only exist in a higher priority source. Null items of
+/// arrays are kept, as they do not name a key.
+pub(crate) fn remove_nulls(value: ConfigValue) -> ConfigValue {
+    match value {
+        ConfigValue::Section(map) => ConfigValue::Section(
+            map.into_iter()
+                .filter(|(_, v)| *v != ConfigValue::Null)
+                .map(|(k, v)| (k, remove_nulls(v)))
+                .collect(),
+        ),
+        ConfigValue::Array(items) => ConfigValue::Array(
+            items
+                .into_iter()
+                .map(remove_nulls)
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Defines how an array from a higher priority source is combined with the
+/// array it overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// The higher priority array replaces the base one. This is the default.
+    #[default]
+    Replace,
+
+    /// Items of the higher priority array are added after the base items.
+    Append,
+
+    /// Items of the higher priority array are added before the base items.
+    Prepend,
+
+    /// Arrays of sections are merged item by item, pairing the items that
+    /// share the same value for the given key. Items without a counterpart
+    /// are appended.
+    ByKey(String),
+}
+
+// Implement `FromStr` trait for `MergeStrategy` so it can be parsed from the
+// `merge = "..."` field attribute.
+impl FromStr for MergeStrategy {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "replace" => Ok(MergeStrategy::Replace),
+            "append" => Ok(MergeStrategy::Append),
+            "prepend" => Ok(MergeStrategy::Prepend),
+            _ => s
+                .strip_prefix("by_key(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| MergeStrategy::ByKey(key.to_string()))
+                .ok_or_else(|| ParserError::InvalidMergeStrategy(s.into())),
+        }
+    }
+}
+
+/// Appends a segment to a dotted key path.
+pub(crate) fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() { segment.to_string() } else { format!("{path}.{segment}") }
+}
+
 /// Merge two `ConfigValue` instances.
 ///
 /// If both values are sections, their internal maps are merged using
@@ -64,23 +182,115 @@ pub fn merge_sections(
 /// using merge_sections. Otherwise, it prioritizes the high ConfigValue,
 /// returning it directly.
 pub fn merge_configs(base: ConfigValue, high: ConfigValue) -> Result<ConfigValue, ParserError> {
+    merge_configs_with(base, high, &HashMap::new())
+}
+
+/// Merge two `ConfigValue` instances, combining arrays according to the given
+/// strategies.
+///
+/// Keys set to `ConfigValue::Null` in `high` are unset, which removes them
+/// from the merged configuration so that defaults and required field checks
+/// apply again, as if no source had set them.
+///
+/// Strategies are keyed by the dotted path of the array they apply to. Items
+/// of arrays are considered to share the path of the array itself, so
+/// `servers.tags` applies to the `tags` array of every item in `servers`.
+/// Arrays without a strategy are replaced, as with `merge_configs`.
+pub fn merge_configs_with(
+    base: ConfigValue,
+    high: ConfigValue,
+    strategies: &HashMap<String, MergeStrategy>,
+) -> Result<ConfigValue, ParserError> {
+    merge_at(base, high, strategies, "")
+}
+
+/// Recursively merges `high` over `base`, where both are found at `path`.
+fn merge_at(
+    base: ConfigValue,
+    high: ConfigValue,
+    strategies: &HashMap<String, MergeStrategy>,
+    path: &str,
+) -> Result<ConfigValue, ParserError> {
     match (base, high) {
         (ConfigValue::Section(mut base_map), ConfigValue::Section(high_map)) => {
             for (k, v_high) in high_map {
-                if let Some(base_val) = base_map.remove(&k) {
-                    let merged = merge_configs(base_val, v_high)?;
+                if v_high == ConfigValue::Null {
+                    base_map.remove(&k);
+                } else if let Some(base_val) = base_map.remove(&k) {
+                    let merged = merge_at(base_val, v_high, strategies, &join_path(path, &k))?;
                     base_map.insert(k, merged);
                 } else {
-                    base_map.insert(k, v_high);
+                    base_map.insert(k, remove_nulls(v_high));
                 }
             }
 
             Ok(ConfigValue::Section(base_map))
         },
-        (_old, high_val) => Ok(high_val),
+        (ConfigValue::Array(mut base_items), ConfigValue::Array(high_items)) => {
+            match strategies.get(path) {
+                Some(MergeStrategy::Append) => {
+                    base_items.extend(
+                        high_items
+                            .into_iter()
+                            .map(remove_nulls),
+                    );
+                    Ok(ConfigValue::Array(base_items))
+                },
+                Some(MergeStrategy::Prepend) => {
+                    let mut items = high_items
+                        .into_iter()
+                        .map(remove_nulls)
+                        .collect::<Vec<_>>();
+                    items.extend(base_items);
+                    Ok(ConfigValue::Array(items))
+                },
+                Some(MergeStrategy::ByKey(key)) => {
+                    merge_by_key(base_items, high_items, key, strategies, path)
+                },
+                Some(MergeStrategy::Replace) | None => {
+                    Ok(remove_nulls(ConfigValue::Array(high_items)))
+                },
+            }
+        },
+        (_old, high_val) => Ok(remove_nulls(high_val)),
     }
 }
 
+/// Merges two arrays of sections, pairing items that share the same value
+/// for `key`.
+fn merge_by_key(
+    mut base_items: Vec<ConfigValue>,
+    high_items: Vec<ConfigValue>,
+    key: &str,
+    strategies: &HashMap<String, MergeStrategy>,
+    path: &str,
+) -> Result<ConfigValue, ParserError> {
+    let identifier = |item: &ConfigValue| match item {
+        ConfigValue::Section(map) => map
+            .get(key)
+            .cloned(),
+        _ => None,
+    };
+
+    for high_item in high_items {
+        let position = identifier(&high_item).and_then(|id| {
+            base_items
+                .iter()
+                .position(|base_item| identifier(base_item).as_ref() == Some(&id))
+        });
+
+        match position {
+            Some(i) => {
+                let base_item = std::mem::replace(&mut base_items[i], ConfigValue::Null);
+                base_items[i] = merge_at(base_item, high_item, strategies, path)?;
+            },
+            None => base_items.push(remove_nulls(high_item)),
+        }
+    }
+
+    Ok(ConfigValue::Array(base_items))
+}
+
 /// Builder for creating a configuration from multiple sources.
 ///
 /// `ConfigBuilder` allows users to specify multiple sources for configuration
@@ -89,12 +299,207 @@ pub fn merge_configs(base: ConfigValue, high: ConfigValue) -> Result<ConfigValue
 pub struct ConfigBuilder {
     /// A list of sources to load configuration from.
     sources: Vec<Box<dyn ConfigSource + Send + Sync>>,
+
+    /// Whether `${VAR}` placeholders are expanded from the environment after
+    /// merging.
+    interpolate: bool,
+
+    /// The explicitly selected profile, if any.
+    profile: Option<String>,
+
+    /// The environment variable used to select a profile when none is
+    /// explicitly selected.
+    profile_env: Option<String>,
+
+    /// Array merge strategies, keyed by the dotted path of the array.
+    merge_strategies: HashMap<String, MergeStrategy>,
+
+    /// The function used to look up environment variables, reading the
+    /// process environment if not set.
+    env: Option<EnvProvider>,
+
+    /// The command-line arguments, read from the process if not set.
+    args: Option<Vec<String>>,
+
+    /// The priority of the command-line source, if enabled.
+    cli: Option<u8>,
+
+    /// The flags known on the command line, if any.
+    cli_definition: Option<CliDefinition>,
+
+    /// Whether the command line is parsed strictly against the definition.
+    strict_cli: bool,
 }
 
 impl ConfigBuilder {
     /// Create an empty configuration builder with no sources.
     pub fn new() -> Self {
-        ConfigBuilder { sources: Vec::new() }
+        ConfigBuilder {
+            sources: Vec::new(),
+            interpolate: false,
+            profile: None,
+            profile_env: None,
+            merge_strategies: HashMap::new(),
+            env: None,
+            args: None,
+            cli: None,
+            cli_definition: None,
+            strict_cli: false,
+        }
+    }
+
+    /// Look up environment variables through the given function instead of
+    /// the process environment.
+    ///
+    /// The provider is used for profile selection, interpolation and field
+    /// overrides alike.
+    pub fn with_env_provider<F>(mut self, provider: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.env = Some(Arc::new(provider));
+        self
+    }
+
+    /// Use the given command-line arguments instead of the ones the process
+    /// was started with.
+    ///
+    /// The arguments must not include the program name.
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = Some(
+            args.into_iter()
+                .map(Into::into)
+                .collect(),
+        );
+        self
+    }
+
+    /// Add the command-line arguments as a source with the given priority.
+    ///
+    /// The arguments set through [`ConfigBuilder::with_args`] are used if
+    /// any, otherwise the process arguments are.
+    pub fn with_cli(mut self, priority: u8) -> Self {
+        self.cli = Some(priority);
+        self
+    }
+
+    /// Parse the command line against the given definition.
+    ///
+    /// Defined flags, and flags named after a key of the definition, set the
+    /// key they are defined for, switches never take a separate value and
+    /// repeatable flags collect an array. Other flags are skipped and
+    /// reported as `UnknownFlag` warnings by the command-line source, unless
+    /// [`ConfigBuilder::with_strict_cli`] is set.
+    pub fn with_cli_definition(mut self, definition: CliDefinition) -> Self {
+        self.cli_definition = Some(definition);
+        self
+    }
+
+    /// Only accept the flags of the definition on the command line.
+    ///
+    /// Once set, the command-line source enabled with
+    /// [`ConfigBuilder::with_cli`] reports `--help`, unknown flags, malformed
+    /// flags and positional arguments as errors instead of ignoring them.
+    /// Has no effect unless a definition is set.
+    pub fn with_strict_cli(mut self) -> Self {
+        self.strict_cli = true;
+        self
+    }
+
+    /// Returns the context fields are loaded with, holding the environment
+    /// provider and command-line arguments of this builder, or the ones of
+    /// the process if not set.
+    ///
+    /// The arguments are parsed against the definition of this builder, if
+    /// any. Build the context once and pass it to
+    /// [`ConfigBuilder::load_with`] to share it with the fields.
+    pub fn context(&self) -> LoadContext {
+        LoadContext::new(
+            self.env_provider(),
+            self.args
+                .clone()
+                .unwrap_or_else(|| {
+                    env::args()
+                        .skip(1)
+                        .collect()
+                }),
+            self.cli_definition
+                .as_ref(),
+        )
+    }
+
+    /// Returns the environment provider of this builder, reading the process
+    /// environment if not set.
+    fn env_provider(&self) -> EnvProvider {
+        self.env
+            .clone()
+            .unwrap_or_else(|| Arc::new(|name| env::var(name).ok()))
+    }
+
+    /// Set how the array at the given dotted path is merged across sources.
+    ///
+    /// Arrays are replaced by higher priority sources unless a strategy is
+    /// set for them.
+    pub fn with_merge_strategy(mut self, path: impl Into<String>, strategy: MergeStrategy) -> Self {
+        self.merge_strategies
+            .insert(path.into(), strategy);
+        self
+    }
+
+    /// Select the profile to load, such as `dev` or `prod`.
+    ///
+    /// Takes precedence over the profile selected through the environment.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Select the profile to load from the given environment variable.
+    ///
+    /// Once set, the `default` profile is used if the variable is not set and
+    /// no profile was explicitly selected.
+    pub fn with_profile_env(mut self, var: impl Into<String>) -> Self {
+        self.profile_env = Some(var.into());
+        self
+    }
+
+    /// Returns the profile that will be loaded, if any.
+    ///
+    /// This is the explicitly selected profile, or else the one named by the
+    /// profile environment variable, falling back to `default` if such a
+    /// variable was configured.
+    pub fn active_profile(&self) -> Option<String> {
+        self.profile_in(&*self.env_provider())
+    }
+
+    /// Returns the active profile, looking up the profile environment
+    /// variable through `var`.
+    fn profile_in(&self, var: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+        if let Some(profile) = &self.profile {
+            return Some(profile.clone());
+        }
+
+        self.profile_env
+            .as_ref()
+            .map(|name| {
+                var(name)
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| "default".to_string())
+            })
+    }
+
+    /// Enable or disable environment variable interpolation.
+    ///
+    /// When enabled, `${VAR}` and `${VAR:-default}` placeholders in the merged
+    /// configuration are expanded from the environment. See
+    /// [`interpolate`] for the supported syntax.
+    pub fn with_interpolation(mut self, enabled: bool) -> Self {
+        self.interpolate = enabled;
+        self
     }
 
     /// Add a configuration source.
@@ -113,16 +518,64 @@ impl ConfigBuilder {
     /// Load and merge all configuration sources.
     ///
     /// Sources are sorted by priority (highest first) and merged sequentially,
-    /// ensuring that later sources override earlier ones.
+    /// ensuring that later sources override earlier ones. If a profile is
+    /// active, each source loads its configuration for that profile. Patch
+    /// sources are applied to the configuration merged so far instead. If
+    /// interpolation is enabled, placeholders are expanded once all sources
+    /// are merged.
     pub fn load(self) -> Result<ConfigValue, ParserError> {
+        let ctx = self.context();
+        self.load_with(&ctx)
+    }
+
+    /// Load and merge all configuration sources like
+    /// [`ConfigBuilder::load`], reading the environment and command-line
+    /// arguments from the given context, as built by
+    /// [`ConfigBuilder::context`].
+    pub fn load_with(self, ctx: &LoadContext) -> Result<ConfigValue, ParserError> {
+        let profile = self.profile_in(&|name| ctx.var(name));
         let mut sources = self.sources;
 
+        if let Some(priority) = self.cli {
+            if let Some(definition) = &self.cli_definition
+                && !self.strict_cli
+            {
+                let names = definition.names();
+
+                for flag in ctx.unknown_flags() {
+                    ctx.warn(ConfigWarning::UnknownFlag {
+                        flag: flag.clone(),
+                        suggestion: suggest(flag, &names).map(str::to_string),
+                    });
+                }
+            }
+
+            let mut cli = CliSource::from_args(
+                ctx.args()
+                    .to_vec(),
+            )
+            .with_priority(priority);
+
+            if let Some(definition) = self.cli_definition {
+                cli = cli.with_definition(definition);
+            }
+
+            if self.strict_cli {
+                cli = cli.with_strict();
+            }
+
+            sources.push(Box::new(cli));
+        }
+
         sources.sort_by_key(|s| Reverse(s.priority()));
 
         let mut accumulated = ConfigValue::Section(HashMap::new());
         for src in sources {
-            let next = src.load()?;
-            accumulated = merge_configs(accumulated, next)?;
+            accumulated = src.apply(accumulated, profile.as_deref(), &self.merge_strategies)?;
+        }
+
+        if self.interpolate {
+            accumulated = interpolate(accumulated, |name| ctx.var(name))?;
         }
 
         Ok(accumulated)
@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use super::ConfigSource;
+use crate::parser::get_file_extension;
+use crate::{ConfigValue, MergeStrategy, ParserError, get_parser};
+
+/// Loads a patch document, inferring its format from the file extension.
+fn load_document(path: &str) -> Result<ConfigValue, ParserError> {
+    get_parser(&get_file_extension(path)?)?.load(path)
+}
+
+/// A configuration source applying an RFC 7396 JSON Merge Patch document.
+///
+/// The patch is applied on top of the configuration merged from every lower
+/// priority source: sections are merged recursively, `null` removes the key
+/// it is set on, and every other value, including arrays, replaces the
+/// patched one. Array merge strategies do not apply to merge patches.
+pub struct MergePatchSource {
+    path: String,
+    priority: u8,
+}
+
+impl MergePatchSource {
+    /// Creates a new `MergePatchSource`.
+    ///
+    /// * `path`: The path to the merge patch document.
+    /// * `priority`: The priority of the patch. The lower the number, the later
+    ///   the patch is applied.
+    pub fn new(path: impl Into<String>, priority: u8) -> Self {
+        MergePatchSource { path: path.into(), priority }
+    }
+}
+
+/// Applies a JSON Merge Patch to `target`, as defined by RFC 7396.
+///
+/// * `target`: The value being patched.
+/// * `patch`: The merge patch document.
+pub fn merge_patch(target: ConfigValue, patch: ConfigValue) -> ConfigValue {
+    match patch {
+        ConfigValue::Section(patch_map) => {
+            let mut map = match target {
+                ConfigValue::Section(map) => map,
+                _ => HashMap::new(),
+            };
+
+            for (k, v) in patch_map {
+                if v == ConfigValue::Null {
+                    map.remove(&k);
+                } else {
+                    let target = map
+                        .remove(&k)
+                        .unwrap_or(ConfigValue::Null);
+                    map.insert(k, merge_patch(target, v));
+                }
+            }
+
+            ConfigValue::Section(map)
+        },
+        patch => patch,
+    }
+}
+
+impl ConfigSource for MergePatchSource {
+    /// Loads the result of applying the patch to an empty configuration.
+    fn load(&self) -> Result<ConfigValue, ParserError> {
+        self.apply(ConfigValue::Section(HashMap::new()), None, &HashMap::new())
+    }
+
+    fn apply(
+        &self,
+        base: ConfigValue,
+        _profile: Option<&str>,
+        _strategies: &HashMap<String, MergeStrategy>,
+    ) -> Result<ConfigValue, ParserError> {
+        Ok(merge_patch(base, load_document(&self.path)?))
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+}
+
+/// A configuration source applying an RFC 6902 JSON Patch document.
+///
+/// The document is an array of operations (`add`, `remove`, `replace`,
+/// `move`, `copy` and `test`) addressing values through JSON Pointers such as
+/// `/server/port`, applied in order on top of the configuration merged from
+/// every lower priority source. Loading fails as soon as an operation cannot
+/// be applied, leaving the configuration untouched.
+pub struct JsonPatchSource {
+    path: String,
+    priority: u8,
+}
+
+impl JsonPatchSource {
+    /// Creates a new `JsonPatchSource`.
+    ///
+    /// * `path`: The path to the JSON Patch document.
+    /// * `priority`: The priority of the patch. The lower the number, the later
+    ///   the patch is applied.
+    pub fn new(path: impl Into<String>, priority: u8) -> Self {
+        JsonPatchSource { path: path.into(), priority }
+    }
+
+    /// Returns an `InvalidPatch` error for this document.
+    fn invalid(&self, reason: impl Into<String>) -> ParserError {
+        ParserError::InvalidPatch {
+            file: self
+                .path
+                .clone(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Loads the document and parses each of its operations.
+    fn operations(&self) -> Result<Vec<PatchOperation>, ParserError> {
+        let ConfigValue::Array(items) = load_document(&self.path)? else {
+            return Err(self.invalid("expected an array of operations"));
+        };
+
+        items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| {
+                PatchOperation::parse(item).map_err(|e| self.invalid(format!("operation {i}: {e}")))
+            })
+            .collect()
+    }
+}
+
+impl ConfigSource for JsonPatchSource {
+    /// Loads the result of applying the patch to an empty configuration.
+    fn load(&self) -> Result<ConfigValue, ParserError> {
+        self.apply(ConfigValue::Section(HashMap::new()), None, &HashMap::new())
+    }
+
+    fn apply(
+        &self,
+        base: ConfigValue,
+        _profile: Option<&str>,
+        _strategies: &HashMap<String, MergeStrategy>,
+    ) -> Result<ConfigValue, ParserError> {
+        self.operations()?
+            .into_iter()
+            .try_fold(base, |value, op| op.apply(value))
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+}
+
+/// Takes a member out of an operation object.
+fn take(map: &mut HashMap<String, ConfigValue>, name: &str) -> Result<ConfigValue, String> {
+    map.remove(name)
+        .ok_or_else(|| format!("missing '{name}' member"))
+}
+
+/// Takes a string member, such as `op` or `path`, out of an operation object.
+fn take_string(map: &mut HashMap<String, ConfigValue>, name: &str) -> Result<String, String> {
+    match take(map, name)? {
+        ConfigValue::Value(s) => Ok(s),
+        other => Err(format!("'{name}' must be a string, found '{other}'")),
+    }
+}
+
+/// A single JSON Patch operation.
+enum PatchOperation {
+    Add { path: String, value: ConfigValue },
+    Remove { path: String },
+    Replace { path: String, value: ConfigValue },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: ConfigValue },
+}
+
+impl PatchOperation {
+    /// Parses an operation from its object representation, such as
+    /// `{ "op": "replace", "path": "/server/port", "value": 8080 }`.
+    fn parse(value: ConfigValue) -> Result<Self, String> {
+        let ConfigValue::Section(mut map) = value else {
+            return Err("expected an object".into());
+        };
+
+        let op = take_string(&mut map, "op")?;
+        let path = take_string(&mut map, "path")?;
+
+        Ok(match op.as_str() {
+            "add" => PatchOperation::Add { path, value: take(&mut map, "value")? },
+            "remove" => PatchOperation::Remove { path },
+            "replace" => PatchOperation::Replace { path, value: take(&mut map, "value")? },
+            "move" => PatchOperation::Move {
+                from: take_string(&mut map, "from")?,
+                path,
+            },
+            "copy" => PatchOperation::Copy {
+                from: take_string(&mut map, "from")?,
+                path,
+            },
+            "test" => PatchOperation::Test { path, value: take(&mut map, "value")? },
+            other => return Err(format!("unknown operation '{other}'")),
+        })
+    }
+
+    /// Returns the name of the operation, as found in the `op` member.
+    fn name(&self) -> &'static str {
+        match self {
+            PatchOperation::Add { .. } => "add",
+            PatchOperation::Remove { .. } => "remove",
+            PatchOperation::Replace { .. } => "replace",
+            PatchOperation::Move { .. } => "move",
+            PatchOperation::Copy { .. } => "copy",
+            PatchOperation::Test { .. } => "test",
+        }
+    }
+
+    /// Returns the JSON Pointer the operation targets.
+    fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. }
+            | PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. }
+            | PatchOperation::Move { path, .. }
+            | PatchOperation::Copy { path, .. }
+            | PatchOperation::Test { path, .. } => path,
+        }
+    }
+
+    /// Applies the operation to `value`.
+    ///
+    /// Returns a `PatchFailed` error if the operation cannot be applied.
+    fn apply(&self, mut value: ConfigValue) -> Result<ConfigValue, ParserError> {
+        self.apply_to(&mut value)
+            .map_err(|reason| ParserError::PatchFailed {
+                op: self
+                    .name()
+                    .to_string(),
+                path: self
+                    .path()
+                    .to_string(),
+                reason,
+            })?;
+
+        Ok(value)
+    }
+
+    /// Applies the operation in place, returning the reason of the failure
+    /// if it cannot be applied.
+    fn apply_to(&self, root: &mut ConfigValue) -> Result<(), String> {
+        match self {
+            PatchOperation::Add { path, value } => add(root, &parse_pointer(path)?, value.clone()),
+            PatchOperation::Remove { path } => remove(root, &parse_pointer(path)?).map(drop),
+            PatchOperation::Replace { path, value } => {
+                let target = get_mut(root, &parse_pointer(path)?)
+                    .ok_or_else(|| "path does not exist".to_string())?;
+                *target = value.clone();
+                Ok(())
+            },
+            PatchOperation::Move { from, path } => {
+                if path.starts_with(&format!("{from}/")) {
+                    return Err(format!("cannot move '{from}' into one of its children"));
+                }
+
+                let value = remove(root, &parse_pointer(from)?)
+                    .map_err(|_| format!("'from' path '{from}' does not exist"))?;
+                add(root, &parse_pointer(path)?, value)
+            },
+            PatchOperation::Copy { from, path } => {
+                let value = get_mut(root, &parse_pointer(from)?)
+                    .ok_or_else(|| format!("'from' path '{from}' does not exist"))?
+                    .clone();
+                add(root, &parse_pointer(path)?, value)
+            },
+            PatchOperation::Test { path, value } => {
+                let found = get_mut(root, &parse_pointer(path)?)
+                    .ok_or_else(|| "path does not exist".to_string())?;
+
+                if found != value {
+                    return Err(format!("expected '{value}', found '{found}'"));
+                }
+
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(format!("'{pointer}' is not a valid JSON Pointer"));
+    };
+
+    Ok(rest
+        .split('/')
+        .map(|token| {
+            token
+                .replace("~1", "/")
+                .replace("~0", "~")
+        })
+        .collect())
+}
+
+/// Parses an array index, which must not have leading zeros.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.len() > 1 && token.starts_with('0') {
+        return None;
+    }
+
+    token
+        .parse()
+        .ok()
+}
+
+/// Looks up the value referenced by the given tokens.
+fn get_mut<'a>(root: &'a mut ConfigValue, tokens: &[String]) -> Option<&'a mut ConfigValue> {
+    tokens
+        .iter()
+        .try_fold(root, |current, token| match current {
+            ConfigValue::Section(map) => map.get_mut(token),
+            ConfigValue::Array(items) => parse_index(token).and_then(|i| items.get_mut(i)),
+            _ => None,
+        })
+}
+
+/// Adds `value` at the location referenced by the given tokens.
+///
+/// Adding to a section sets the key, while adding to an array inserts the
+/// value at the index, or at the end for the `-` token.
+fn add(root: &mut ConfigValue, tokens: &[String], value: ConfigValue) -> Result<(), String> {
+    let Some((last, parent)) = tokens.split_last() else {
+        *root = value;
+        return Ok(());
+    };
+
+    match get_mut(root, parent) {
+        Some(ConfigValue::Section(map)) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        },
+        Some(ConfigValue::Array(items)) => {
+            let index = match last.as_str() {
+                "-" => items.len(),
+                token => parse_index(token)
+                    .filter(|i| *i <= items.len())
+                    .ok_or_else(|| format!("'{token}' is not a valid index"))?,
+            };
+
+            items.insert(index, value);
+            Ok(())
+        },
+        Some(other) => Err(format!("cannot add a value to '{other}'")),
+        None => Err("parent path does not exist".into()),
+    }
+}
+
+/// Removes the value referenced by the given tokens, returning it.
+fn remove(root: &mut ConfigValue, tokens: &[String]) -> Result<ConfigValue, String> {
+    let Some((last, parent)) = tokens.split_last() else {
+        return Err("cannot remove the whole document".into());
+    };
+
+    let removed = match get_mut(root, parent) {
+        Some(ConfigValue::Section(map)) => map.remove(last),
+        Some(ConfigValue::Array(items)) => parse_index(last)
+            .filter(|i| *i < items.len())
+            .map(|i| items.remove(i)),
+        _ => None,
+    };
+
+    removed.ok_or_else(|| "path does not exist".to_string())
+}
@@ -1 +1,10 @@
+#[cfg(feature = "clap")]
+mod test_clap;
+mod test_cli;
+mod test_context;
+mod test_include;
+mod test_interpolate;
 mod test_merge;
+mod test_patch;
+mod test_profile;
+mod test_warning;
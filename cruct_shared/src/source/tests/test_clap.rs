@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use clap::{Args, Command, FromArgMatches};
+
+use crate::{
+    ClapSource,
+    CliDefinition,
+    CliFlag,
+    ConfigArgs,
+    ConfigSource,
+    ConfigValue,
+    FromConfigValue,
+    ParserError,
+};
+
+struct Settings;
+
+impl FromConfigValue for Settings {
+    fn from_config_value(_value: &ConfigValue) -> Result<Self, ParserError> {
+        Ok(Settings)
+    }
+
+    fn cli_flags() -> Vec<CliFlag> {
+        let flag = |key: &str, ty: &str| CliFlag {
+            key: key.to_string(),
+            arg: None,
+            ty: ty.to_string(),
+            default: None,
+            env: None,
+            description: None,
+        };
+
+        vec![
+            CliFlag {
+                arg: Some("port".into()),
+                ..flag("server.port", "u16")
+            },
+            flag("verbose", "bool"),
+            flag("tags", "Vec<String>"),
+            flag("name", "String"),
+        ]
+    }
+}
+
+fn value(v: &str) -> ConfigValue {
+    ConfigValue::Value(v.to_string())
+}
+
+#[test]
+fn clap_matches_are_loaded_as_source() {
+    let definition = CliDefinition::new("app", Settings::cli_flags());
+    let matches = definition
+        .augment_args(Command::new("app"))
+        .try_get_matches_from(["app", "--port", "80", "--verbose", "--tags", "a", "--tags=b"])
+        .unwrap();
+
+    let src = ClapSource::from_matches(&matches, &definition).with_priority(2);
+
+    assert_eq!(src.priority(), 2);
+    assert_eq!(
+        src.load()
+            .unwrap(),
+        ConfigValue::Section(HashMap::from([
+            (
+                "server".to_string(),
+                ConfigValue::Section(HashMap::from([("port".to_string(), value("80"))])),
+            ),
+            ("verbose".to_string(), value("true")),
+            ("tags".to_string(), ConfigValue::Array(vec![value("a"), value("b")])),
+        ]))
+    );
+}
+
+#[test]
+fn config_args_flatten_into_commands() {
+    let cmd =
+        ConfigArgs::<Settings>::augment_args(Command::new("app").arg(clap::Arg::new("input")));
+    let matches = cmd
+        .try_get_matches_from(["app", "input.txt", "--name", "x"])
+        .unwrap();
+
+    assert_eq!(
+        matches
+            .get_one::<String>("input")
+            .map(String::as_str),
+        Some("input.txt")
+    );
+
+    let args = ConfigArgs::<Settings>::from_arg_matches(&matches).unwrap();
+
+    assert_eq!(
+        args.source()
+            .load()
+            .unwrap(),
+        ConfigValue::Section(HashMap::from([("name".to_string(), value("x"))]))
+    );
+}
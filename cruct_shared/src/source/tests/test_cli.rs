@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::source::cli::parse_args;
+use crate::{
+    CliDefinition,
+    CliFlag,
+    CliSource,
+    ConfigBuilder,
+    ConfigSource,
+    ConfigValue,
+    LoadContext,
+    ParserError,
+};
+
+fn parse(args: &[&str]) -> HashMap<String, ConfigValue> {
+    let ConfigValue::Section(map) = parse_args(
+        args.iter()
+            .map(|arg| arg.to_string()),
+        None,
+    ) else {
+        panic!("expected section");
+    };
+
+    map
+}
+
+fn value(v: &str) -> ConfigValue {
+    ConfigValue::Value(v.to_string())
+}
+
+#[test]
+fn parses_equals_and_separate_values() {
+    let map = parse(&["--host=localhost", "--port", "8080", "--offset", "-5"]);
+
+    assert_eq!(map["host"], value("localhost"));
+    assert_eq!(map["port"], value("8080"));
+    assert_eq!(map["offset"], value("-5"));
+}
+
+#[test]
+fn parses_boolean_flags() {
+    let map = parse(&["--verbose", "--no-color", "--debug", "--level=2"]);
+
+    assert_eq!(map["verbose"], value("true"));
+    assert_eq!(map["color"], value("false"));
+    assert_eq!(map["debug"], value("true"));
+    assert_eq!(map["level"], value("2"));
+}
+
+#[test]
+fn repeated_flags_collect_arrays() {
+    let map = parse(&["--tag", "a", "--tag=b", "--tag", "c", "--single", "x"]);
+
+    assert_eq!(map["tag"], ConfigValue::Array(vec![value("a"), value("b"), value("c")]));
+    assert_eq!(map["single"], value("x"));
+}
+
+#[test]
+fn dotted_keys_build_nested_sections() {
+    let map = parse(&["--database.port=5432", "--database.host", "db", "--database.pool.size=4"]);
+
+    assert_eq!(
+        map["database"],
+        ConfigValue::Section(HashMap::from([
+            ("port".to_string(), value("5432")),
+            ("host".to_string(), value("db")),
+            (
+                "pool".to_string(),
+                ConfigValue::Section(HashMap::from([("size".to_string(), value("4"))])),
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn terminator_and_positional_arguments_are_ignored() {
+    let map = parse(&["input.txt", "-v", "--name=app", "--", "--port=1"]);
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map["name"], value("app"));
+}
+
+#[test]
+fn from_args_parses_given_arguments() {
+    let src = CliSource::from_args(["--port=1", "--db.host", "x"]).with_priority(3);
+
+    assert_eq!(src.priority(), 3);
+    assert_eq!(
+        src.load()
+            .unwrap(),
+        ConfigValue::Section(HashMap::from([
+            ("port".to_string(), value("1")),
+            (
+                "db".to_string(),
+                ConfigValue::Section(HashMap::from([("host".to_string(), value("x"))])),
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn context_looks_up_flags_and_variables() {
+    let ctx = LoadContext::new(
+        Arc::new(|name| (name == "HOME").then(|| "/home/app".to_string())),
+        vec!["--db.port".into(), "5432".into(), "--verbose".into()],
+        None,
+    );
+
+    assert_eq!(ctx.arg("db.port"), Some(value("5432")));
+    assert_eq!(ctx.arg("db"), None);
+    assert_eq!(ctx.arg("verbose"), Some(value("true")));
+    assert_eq!(ctx.arg("missing"), None);
+    assert_eq!(
+        ctx.var("HOME")
+            .as_deref(),
+        Some("/home/app")
+    );
+    assert_eq!(ctx.var("USER"), None);
+}
+
+#[test]
+fn builder_uses_injected_arguments_and_environment() {
+    let cfg = ConfigBuilder::new()
+        .with_cli(0)
+        .with_args(["--url=${HOST}:80"])
+        .with_env_provider(|name| (name == "HOST").then(|| "example.com".to_string()))
+        .with_interpolation(true)
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        cfg,
+        ConfigValue::Section(HashMap::from([("url".to_string(), value("example.com:80"))]))
+    );
+}
+
+fn flag(key: &str, ty: &str) -> CliFlag {
+    CliFlag {
+        key: key.to_string(),
+        arg: None,
+        ty: ty.to_string(),
+        default: None,
+        env: None,
+        description: None,
+    }
+}
+
+fn definition() -> CliDefinition {
+    CliDefinition::new(
+        "app",
+        vec![
+            CliFlag {
+                arg: Some("port".into()),
+                default: Some("8080".into()),
+                env: Some("APP_PORT".into()),
+                description: Some("The port to listen on.".into()),
+                ..flag("server.port", "u16")
+            },
+            flag("verbose", "bool"),
+            flag("tags", "Vec<String>"),
+        ],
+    )
+}
+
+fn parse_strict(args: &[&str]) -> Result<ConfigValue, ParserError> {
+    definition().parse(
+        args.iter()
+            .map(|arg| arg.to_string()),
+    )
+}
+
+#[test]
+fn definition_maps_flags_to_keys() {
+    let cfg = parse_strict(&["--port", "80", "--no-verbose", "--tags=a", "--tags", "b"]).unwrap();
+
+    assert_eq!(
+        cfg,
+        ConfigValue::Section(HashMap::from([
+            (
+                "server".to_string(),
+                ConfigValue::Section(HashMap::from([("port".to_string(), value("80"))])),
+            ),
+            ("verbose".to_string(), value("false")),
+            ("tags".to_string(), ConfigValue::Array(vec![value("a"), value("b")])),
+        ]))
+    );
+}
+
+#[test]
+fn definition_decides_which_flags_take_values() {
+    let definition = CliDefinition {
+        keys: vec![flag("db.host", "String")],
+        ..definition()
+    };
+    let cfg = parse_args(
+        ["--verbose", "input.txt", "--tags", "a", "--port", "80", "--unknown", "x", "--db.host=h"]
+            .map(String::from),
+        Some(&definition),
+    );
+
+    assert_eq!(
+        cfg,
+        ConfigValue::Section(HashMap::from([
+            (
+                "server".to_string(),
+                ConfigValue::Section(HashMap::from([("port".to_string(), value("80"))])),
+            ),
+            ("verbose".to_string(), value("true")),
+            ("tags".to_string(), ConfigValue::Array(vec![value("a")])),
+            (
+                "db".to_string(),
+                ConfigValue::Section(HashMap::from([("host".to_string(), value("h"))])),
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn source_is_lenient_unless_strict() {
+    let args = ["positional", "--verbose", "input.txt", "--other"];
+    let lenient = CliSource::from_args(args).with_definition(definition());
+
+    assert_eq!(
+        lenient
+            .load()
+            .unwrap(),
+        ConfigValue::Section(HashMap::from([("verbose".to_string(), value("true"))]))
+    );
+    assert!(matches!(
+        lenient
+            .with_strict()
+            .load(),
+        Err(ParserError::MalformedFlag { flag, .. }) if flag == "positional"
+    ));
+}
+
+#[test]
+fn context_parses_arguments_against_definition() {
+    let args = vec!["--verbose".to_string(), "input.txt".to_string()];
+
+    let ctx = LoadContext::new(Arc::new(|_| None), args.clone(), None);
+    assert_eq!(ctx.arg("verbose"), Some(value("input.txt")));
+
+    let ctx = LoadContext::new(Arc::new(|_| None), args, Some(&definition()));
+    assert_eq!(ctx.arg("verbose"), Some(value("true")));
+    assert!(
+        ctx.unknown_flags()
+            .is_empty()
+    );
+
+    let ctx = LoadContext::new(
+        Arc::new(|_| None),
+        vec!["--help".into(), "--port=80".into()],
+        Some(&definition()),
+    );
+    assert_eq!(ctx.unknown_flags(), ["help"]);
+
+    let default = LoadContext::default();
+    assert!(
+        default
+            .args()
+            .is_empty()
+    );
+    assert_eq!(default.var("PATH"), None);
+}
+
+#[test]
+fn definition_rejects_unknown_and_malformed_flags() {
+    assert!(matches!(
+        parse_strict(&["--server.port=80"]),
+        Err(ParserError::UnknownFlag(flag)) if flag == "server.port"
+    ));
+
+    for args in [
+        &["--port"][..],
+        &["--port", "--verbose"],
+        &["--port=1", "--port=2"],
+        &["--no-verbose=true"],
+        &["input.txt"],
+        &["-p", "80"],
+    ] {
+        assert!(
+            matches!(parse_strict(args), Err(ParserError::MalformedFlag { .. })),
+            "expected {args:?} to be malformed"
+        );
+    }
+
+    assert!(parse_strict(&["--verbose", "--", "input.txt", "--unknown"]).is_ok());
+}
+
+#[test]
+fn definition_renders_help() {
+    let Err(ParserError::HelpRequested(help)) = parse_strict(&["--port=80", "--help"]) else {
+        panic!("expected help to be requested");
+    };
+
+    assert_eq!(help, definition().help());
+    assert_eq!(
+        help,
+        concat!(
+            "Usage: app [OPTIONS]\n",
+            "\n",
+            "Options:\n",
+            "      --port <u16>          The port to listen on. [default: 8080] [env: APP_PORT]\n",
+            "      --verbose\n",
+            "      --tags <Vec<String>>\n",
+            "  -h, --help                Print help\n",
+        )
+    );
+}
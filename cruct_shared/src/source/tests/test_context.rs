@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::{ConfigWarning, LoadContext};
+
+#[test]
+fn nested_contexts_share_warnings() {
+    let ctx = LoadContext::new(Arc::new(|_| None), Vec::new(), None);
+    let nested = ctx
+        .nested("database")
+        .nested("0");
+
+    assert_eq!(ctx.path_of("port"), "port");
+    assert_eq!(nested.path_of("url"), "database.0.url");
+
+    nested.warn(ConfigWarning::DeprecatedKey {
+        key: nested.path_of("uri"),
+        message: "use `url` instead".into(),
+    });
+
+    assert_eq!(
+        ctx.take_warnings(),
+        [ConfigWarning::DeprecatedKey {
+            key: "database.0.uri".into(),
+            message: "use `url` instead".into(),
+        }]
+    );
+    assert!(
+        nested
+            .take_warnings()
+            .is_empty()
+    );
+}
+
+#[test]
+fn unknown_keys_are_taken_by_section() {
+    let ctx = LoadContext::new(Arc::new(|_| None), Vec::new(), None);
+    let database = ctx.nested("database");
+    let known = ["url".to_string()];
+
+    database.unknown_keys(&[String::from("uri")], &["url"]);
+    ctx.unknown_keys(&known, &[]);
+    ctx.nested("databases")
+        .unknown_keys(&known, &[]);
+
+    assert_eq!(database.take_unknown_keys(), ["'database.uri' (did you mean `url`?)"]);
+    assert_eq!(ctx.take_unknown_keys(), ["'databases.url'", "'url'"]);
+    assert!(
+        ctx.take_warnings()
+            .is_empty()
+    );
+}
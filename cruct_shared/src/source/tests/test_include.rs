@@ -0,0 +1,150 @@
+use std::fs::{create_dir, write};
+
+use tempfile::tempdir;
+
+use crate::{ConfigFileSource, ConfigSource, ConfigValue, ParserError};
+
+#[test]
+fn included_values_are_merged_beneath_including_file() {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path()
+            .join("common.toml"),
+        "host = 'localhost'\nport = 80",
+    )
+    .unwrap();
+    write(
+        dir.path()
+            .join("main.toml"),
+        "include = ['common.toml']\nport = 8080",
+    )
+    .unwrap();
+
+    let cfg = ConfigFileSource::new(
+        dir.path()
+            .join("main.toml")
+            .to_str()
+            .unwrap(),
+        None,
+    )
+    .load()
+    .unwrap();
+
+    let ConfigValue::Section(map) = cfg else {
+        panic!("expected section");
+    };
+
+    assert_eq!(map["host"], ConfigValue::Value("localhost".into()));
+    assert_eq!(map["port"], ConfigValue::Value("8080".into()));
+    assert!(!map.contains_key("include"));
+}
+
+#[test]
+fn includes_resolve_relative_to_including_file_and_expand_globs() {
+    let dir = tempdir().unwrap();
+    create_dir(
+        dir.path()
+            .join("secrets"),
+    )
+    .unwrap();
+    write(
+        dir.path()
+            .join("secrets/a.yml"),
+        "password: first\nuser: admin",
+    )
+    .unwrap();
+    write(
+        dir.path()
+            .join("secrets/b.json"),
+        r#"{"password": "second"}"#,
+    )
+    .unwrap();
+    write(
+        dir.path()
+            .join("main.json"),
+        r#"{"$include": "secrets/*", "name": "app"}"#,
+    )
+    .unwrap();
+
+    let cfg = ConfigFileSource::new(
+        dir.path()
+            .join("main.json")
+            .to_str()
+            .unwrap(),
+        None,
+    )
+    .load()
+    .unwrap();
+
+    let ConfigValue::Section(map) = cfg else {
+        panic!("expected section");
+    };
+
+    // Glob matches are merged alphabetically, so `b.json` wins over `a.yml`.
+    assert_eq!(map["password"], ConfigValue::Value("second".into()));
+    assert_eq!(map["user"], ConfigValue::Value("admin".into()));
+    assert_eq!(map["name"], ConfigValue::Value("app".into()));
+}
+
+#[test]
+fn include_cycles_are_reported() {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path()
+            .join("a.toml"),
+        "include = 'b.toml'",
+    )
+    .unwrap();
+    write(
+        dir.path()
+            .join("b.toml"),
+        "include = 'a.toml'",
+    )
+    .unwrap();
+
+    let err = ConfigFileSource::new(
+        dir.path()
+            .join("a.toml")
+            .to_str()
+            .unwrap(),
+        None,
+    )
+    .load()
+    .unwrap_err();
+
+    let ParserError::IncludeCycle(chain) = err else {
+        panic!("expected include cycle, found {err}");
+    };
+
+    let files = chain
+        .split(" -> ")
+        .collect::<Vec<_>>();
+
+    assert_eq!(files.len(), 3);
+    assert!(files[0].ends_with("a.toml"));
+    assert!(files[1].ends_with("b.toml"));
+    assert!(files[2].ends_with("a.toml"));
+}
+
+#[test]
+fn invalid_include_directive_is_reported() {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path()
+            .join("main.toml"),
+        "[include]\nkey = 1",
+    )
+    .unwrap();
+
+    let err = ConfigFileSource::new(
+        dir.path()
+            .join("main.toml")
+            .to_str()
+            .unwrap(),
+        None,
+    )
+    .load()
+    .unwrap_err();
+
+    assert!(matches!(err, ParserError::InvalidInclude { .. }));
+}
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use crate::{ConfigValue, ParserError, interpolate};
+
+fn lookup(name: &str) -> Option<String> {
+    match name {
+        "HOST" => Some("db.local".into()),
+        "EMPTY" => Some(String::new()),
+        _ => None,
+    }
+}
+
+fn section(entries: &[(&str, &str)]) -> ConfigValue {
+    ConfigValue::Section(
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), ConfigValue::Value(v.to_string())))
+            .collect(),
+    )
+}
+
+#[test]
+fn expands_variables_and_fallbacks() {
+    let value = section(&[
+        ("url", "postgres://${HOST}/app"),
+        ("port", "${PORT:-8080}"),
+        ("empty", "${EMPTY:-fallback}"),
+    ]);
+
+    let result = interpolate(value, lookup).unwrap();
+
+    assert_eq!(
+        result,
+        section(&[("url", "postgres://db.local/app"), ("port", "8080"), ("empty", "fallback"),])
+    );
+}
+
+#[test]
+fn escaped_and_incomplete_placeholders_are_kept() {
+    let value = section(&[("price", "$5 and $${HOST}"), ("open", "${HOST")]);
+
+    let result = interpolate(value, lookup).unwrap();
+
+    assert_eq!(result, section(&[("price", "$5 and ${HOST}"), ("open", "${HOST")]));
+}
+
+#[test]
+fn expands_inside_nested_sections_and_arrays() {
+    let value = ConfigValue::Section(HashMap::from([(
+        "hosts".to_string(),
+        ConfigValue::Array(vec![ConfigValue::Value("${HOST}".into()), ConfigValue::Null]),
+    )]));
+
+    let result = interpolate(value, lookup).unwrap();
+
+    assert_eq!(
+        result,
+        ConfigValue::Section(HashMap::from([(
+            "hosts".to_string(),
+            ConfigValue::Array(vec![ConfigValue::Value("db.local".into()), ConfigValue::Null]),
+        )]))
+    );
+}
+
+#[test]
+fn unresolved_variables_are_listed() {
+    let value = section(&[("credentials", "${PASSWORD}@${USER}")]);
+
+    let err = interpolate(value, lookup).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Unresolved variables: credentials -> PASSWORD, credentials -> USER"
+    );
+}
+
+#[test]
+fn values_reference_other_keys() {
+    let value = ConfigValue::Section(HashMap::from([
+        ("paths".to_string(), section(&[("base", "/srv/${HOST}")])),
+        ("log_dir".to_string(), ConfigValue::Value("${paths.base}/logs".into())),
+        ("servers".to_string(), ConfigValue::Array(vec![section(&[("host", "${HOST}")])])),
+        ("primary".to_string(), ConfigValue::Value("${servers.0.host}".into())),
+    ]));
+
+    let ConfigValue::Section(result) = interpolate(value, lookup).unwrap() else {
+        panic!("expected section");
+    };
+
+    assert_eq!(result["log_dir"], ConfigValue::Value("/srv/db.local/logs".into()));
+    assert_eq!(result["primary"], ConfigValue::Value("db.local".into()));
+}
+
+#[test]
+fn undefined_references_report_the_full_chain() {
+    let value = section(&[("log_dir", "${base}/logs"), ("base", "${ROOT}/srv")]);
+
+    let err = interpolate(value, lookup).unwrap_err();
+
+    let ParserError::UnresolvedVariables(chains) = err else {
+        panic!("expected unresolved variables, found {err}");
+    };
+
+    assert!(chains.contains(&"log_dir -> base -> ROOT".to_string()));
+    assert!(chains.contains(&"base -> ROOT".to_string()));
+}
+
+#[test]
+fn reference_cycles_are_detected() {
+    let value = section(&[("a", "${b}"), ("b", "x-${a}")]);
+
+    let err = interpolate(value, lookup).unwrap_err();
+
+    assert!(matches!(
+        err,
+        ParserError::ReferenceCycle(cycle) if cycle == "a -> b -> a" || cycle == "b -> a -> b"
+    ));
+}
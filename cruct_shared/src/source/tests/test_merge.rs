@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
-use crate::ConfigValue;
-use crate::source::{merge_configs, merge_sections};
+use crate::source::{merge_configs, merge_configs_with, merge_sections};
+use crate::{ConfigValue, MergeStrategy, ParserError};
 
 #[test]
 fn overrides_and_merges_nested_sections() {
@@ -114,3 +114,190 @@ fn merge_configs_non_sections() {
 
     assert_eq!(result, ConfigValue::Value("high_value".to_string()));
 }
+
+fn values(items: &[&str]) -> ConfigValue {
+    ConfigValue::Array(
+        items
+            .iter()
+            .map(|v| ConfigValue::Value(v.to_string()))
+            .collect(),
+    )
+}
+
+fn server(name: &str, port: &str) -> ConfigValue {
+    ConfigValue::Section(HashMap::from([
+        ("name".to_string(), ConfigValue::Value(name.into())),
+        ("port".to_string(), ConfigValue::Value(port.into())),
+    ]))
+}
+
+fn with_key(key: &str, value: ConfigValue) -> ConfigValue {
+    ConfigValue::Section(HashMap::from([(key.to_string(), value)]))
+}
+
+#[test]
+fn arrays_are_replaced_by_default() {
+    let base = with_key("plugins", values(&["a", "b"]));
+    let high = with_key("plugins", values(&["c"]));
+
+    let result = merge_configs(base, high).unwrap();
+
+    assert_eq!(result, with_key("plugins", values(&["c"])));
+}
+
+#[test]
+fn append_and_prepend_strategies() {
+    let strategies = HashMap::from([
+        ("plugins".to_string(), MergeStrategy::Append),
+        ("nested.hosts".to_string(), MergeStrategy::Prepend),
+    ]);
+
+    let base = ConfigValue::Section(HashMap::from([
+        ("plugins".to_string(), values(&["a", "b"])),
+        ("nested".to_string(), with_key("hosts", values(&["x"]))),
+    ]));
+    let high = ConfigValue::Section(HashMap::from([
+        ("plugins".to_string(), values(&["c"])),
+        ("nested".to_string(), with_key("hosts", values(&["y"]))),
+    ]));
+
+    let result = merge_configs_with(base, high, &strategies).unwrap();
+
+    assert_eq!(
+        result,
+        ConfigValue::Section(HashMap::from([
+            ("plugins".to_string(), values(&["a", "b", "c"])),
+            ("nested".to_string(), with_key("hosts", values(&["y", "x"]))),
+        ]))
+    );
+}
+
+#[test]
+fn by_key_strategy_merges_matching_items() {
+    let strategies =
+        HashMap::from([("servers".to_string(), MergeStrategy::ByKey("name".to_string()))]);
+
+    let base = with_key(
+        "servers",
+        ConfigValue::Array(vec![server("primary", "80"), server("replica", "81")]),
+    );
+    let high = with_key(
+        "servers",
+        ConfigValue::Array(vec![server("replica", "9000"), server("backup", "82")]),
+    );
+
+    let result = merge_configs_with(base, high, &strategies).unwrap();
+
+    assert_eq!(
+        result,
+        with_key(
+            "servers",
+            ConfigValue::Array(vec![
+                server("primary", "80"),
+                server("replica", "9000"),
+                server("backup", "82"),
+            ])
+        )
+    );
+}
+
+#[test]
+fn merge_strategy_from_str() {
+    assert_eq!(
+        "append"
+            .parse::<MergeStrategy>()
+            .unwrap(),
+        MergeStrategy::Append
+    );
+    assert_eq!(
+        "prepend"
+            .parse::<MergeStrategy>()
+            .unwrap(),
+        MergeStrategy::Prepend
+    );
+    assert_eq!(
+        "replace"
+            .parse::<MergeStrategy>()
+            .unwrap(),
+        MergeStrategy::Replace
+    );
+    assert_eq!(
+        "by_key(name)"
+            .parse::<MergeStrategy>()
+            .unwrap(),
+        MergeStrategy::ByKey("name".into())
+    );
+    assert!(matches!(
+        "by_key()".parse::<MergeStrategy>(),
+        Err(ParserError::InvalidMergeStrategy(s)) if s == "by_key()"
+    ));
+}
+
+#[test]
+fn null_unsets_inherited_keys() {
+    let base = ConfigValue::Section(HashMap::from([
+        ("host".to_string(), ConfigValue::Value("localhost".to_string())),
+        ("port".to_string(), ConfigValue::Value("80".to_string())),
+        (
+            "nested".to_string(),
+            ConfigValue::Section(HashMap::from([(
+                "key".to_string(),
+                ConfigValue::Value("value".to_string()),
+            )])),
+        ),
+    ]));
+
+    let high = ConfigValue::Section(HashMap::from([
+        ("port".to_string(), ConfigValue::Null),
+        (
+            "nested".to_string(),
+            ConfigValue::Section(HashMap::from([("key".to_string(), ConfigValue::Null)])),
+        ),
+    ]));
+
+    let merged = merge_configs(base, high).unwrap();
+
+    assert_eq!(
+        merged,
+        ConfigValue::Section(HashMap::from([
+            ("host".to_string(), ConfigValue::Value("localhost".to_string())),
+            ("nested".to_string(), ConfigValue::Section(HashMap::new())),
+        ]))
+    );
+}
+
+#[test]
+fn null_is_removed_from_sections_without_counterpart() {
+    let base = ConfigValue::Section(HashMap::new());
+    let high = ConfigValue::Section(HashMap::from([
+        ("missing".to_string(), ConfigValue::Null),
+        (
+            "servers".to_string(),
+            ConfigValue::Array(vec![ConfigValue::Section(HashMap::from([
+                ("name".to_string(), ConfigValue::Value("a".to_string())),
+                ("port".to_string(), ConfigValue::Null),
+            ]))]),
+        ),
+    ]));
+
+    let merged = merge_configs(base, high).unwrap();
+
+    assert_eq!(
+        merged,
+        ConfigValue::Section(HashMap::from([(
+            "servers".to_string(),
+            ConfigValue::Array(vec![ConfigValue::Section(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::Value("a".to_string()),
+            )]))]),
+        )]))
+    );
+}
+
+#[test]
+fn merge_sections_unsets_null_keys() {
+    let base = HashMap::from([("a".to_string(), ConfigValue::Value("one".to_string()))]);
+    let high = HashMap::from([("a".to_string(), ConfigValue::Null)]);
+
+    assert!(merge_sections(base, high).is_empty());
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::fs::write;
+
+use tempfile::tempdir;
+
+use crate::{
+    ConfigBuilder,
+    ConfigFileSource,
+    ConfigSource,
+    ConfigValue,
+    JsonPatchSource,
+    MergePatchSource,
+    ParserError,
+    merge_patch,
+};
+
+fn section<const N: usize>(entries: [(&str, ConfigValue); N]) -> ConfigValue {
+    ConfigValue::Section(
+        entries
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    )
+}
+
+fn value(v: &str) -> ConfigValue {
+    ConfigValue::Value(v.to_string())
+}
+
+#[test]
+fn merge_patch_follows_rfc_7396() {
+    let target = section([
+        ("title", value("Goodbye!")),
+        ("author", section([("given", value("John")), ("family", value("Doe"))])),
+        ("tags", ConfigValue::Array(vec![value("example"), value("sample")])),
+        ("content", value("This will be unchanged")),
+    ]);
+
+    let patch = section([
+        ("title", value("Hello!")),
+        ("phone", value("+01-123-456-7890")),
+        ("author", section([("family", ConfigValue::Null)])),
+        ("tags", ConfigValue::Array(vec![value("example")])),
+    ]);
+
+    assert_eq!(
+        merge_patch(target, patch),
+        section([
+            ("title", value("Hello!")),
+            ("author", section([("given", value("John"))])),
+            ("tags", ConfigValue::Array(vec![value("example")])),
+            ("content", value("This will be unchanged")),
+            ("phone", value("+01-123-456-7890")),
+        ])
+    );
+}
+
+#[test]
+fn merge_patch_replaces_non_section_targets() {
+    assert_eq!(merge_patch(value("a"), section([("b", value("c"))])), section([("b", value("c"))]));
+    assert_eq!(merge_patch(section([("a", value("b"))]), value("c")), value("c"));
+}
+
+#[test]
+fn patch_sources_apply_over_merged_configuration() {
+    let dir = tempdir().unwrap();
+    let base = dir
+        .path()
+        .join("base.toml");
+    let merge = dir
+        .path()
+        .join("merge.json");
+    let patch = dir
+        .path()
+        .join("patch.json");
+
+    write(&base, "plugins = ['auth']\n[server]\nhost = 'localhost'\nport = 80").unwrap();
+    write(&merge, r#"{"plugins": ["metrics"], "server": {"host": null}}"#).unwrap();
+    write(
+        &patch,
+        r#"[
+            {"op": "test", "path": "/server/port", "value": 80},
+            {"op": "replace", "path": "/server/port", "value": 8080},
+            {"op": "add", "path": "/plugins/-", "value": "tracing"},
+            {"op": "copy", "from": "/server/port", "path": "/admin_port"},
+            {"op": "move", "from": "/plugins/0", "path": "/primary_plugin"}
+        ]"#,
+    )
+    .unwrap();
+
+    let cfg = ConfigBuilder::new()
+        .add_source(ConfigFileSource::new(
+            base.to_str()
+                .unwrap(),
+            None,
+        ))
+        .add_source(MergePatchSource::new(
+            merge
+                .to_str()
+                .unwrap(),
+            1,
+        ))
+        .add_source(JsonPatchSource::new(
+            patch
+                .to_str()
+                .unwrap(),
+            0,
+        ))
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        cfg,
+        section([
+            ("plugins", ConfigValue::Array(vec![value("tracing")])),
+            ("primary_plugin", value("metrics")),
+            ("server", section([("port", value("8080"))])),
+            ("admin_port", value("8080")),
+        ])
+    );
+}
+
+#[test]
+fn json_pointer_tokens_are_unescaped() {
+    let dir = tempdir().unwrap();
+    let patch = dir
+        .path()
+        .join("patch.json");
+
+    write(
+        &patch,
+        r#"[
+            {"op": "add", "path": "/a~1b", "value": 1},
+            {"op": "add", "path": "/m~0n", "value": 2}
+        ]"#,
+    )
+    .unwrap();
+
+    let cfg = JsonPatchSource::new(
+        patch
+            .to_str()
+            .unwrap(),
+        0,
+    )
+    .load()
+    .unwrap();
+
+    assert_eq!(
+        cfg,
+        ConfigValue::Section(HashMap::from([
+            ("a/b".to_string(), value("1")),
+            ("m~n".to_string(), value("2")),
+        ]))
+    );
+}
+
+#[test]
+fn failed_operations_are_reported() {
+    let dir = tempdir().unwrap();
+    let cases = [
+        (r#"[{"op": "replace", "path": "/missing", "value": 1}]"#, "replace", "/missing"),
+        (r#"[{"op": "remove", "path": "/server/missing"}]"#, "remove", "/server/missing"),
+        (r#"[{"op": "test", "path": "/server/port", "value": 81}]"#, "test", "/server/port"),
+        (r#"[{"op": "add", "path": "/missing/key", "value": 1}]"#, "add", "/missing/key"),
+        (
+            r#"[{"op": "move", "from": "/server", "path": "/server/inner"}]"#,
+            "move",
+            "/server/inner",
+        ),
+    ];
+
+    for (document, expected_op, expected_path) in cases {
+        let patch = dir
+            .path()
+            .join("patch.json");
+        write(&patch, document).unwrap();
+
+        let base = section([("server", section([("port", value("80"))]))]);
+        let err = JsonPatchSource::new(
+            patch
+                .to_str()
+                .unwrap(),
+            0,
+        )
+        .apply(base, None, &HashMap::new())
+        .unwrap_err();
+
+        let ParserError::PatchFailed { op, path, .. } = err else {
+            panic!("expected a failed patch, found {err}");
+        };
+
+        assert_eq!(op, expected_op);
+        assert_eq!(path, expected_path);
+    }
+}
+
+#[test]
+fn malformed_operations_are_reported() {
+    let dir = tempdir().unwrap();
+    let documents = [
+        r#"{"op": "add"}"#,
+        r#"[{"op": "add", "path": "/a"}]"#,
+        r#"[{"op": "rename", "path": "/a"}]"#,
+        r#"[{"path": "/a"}]"#,
+    ];
+
+    for document in documents {
+        let patch = dir
+            .path()
+            .join("patch.json");
+        write(&patch, document).unwrap();
+
+        let err = JsonPatchSource::new(
+            patch
+                .to_str()
+                .unwrap(),
+            0,
+        )
+        .load()
+        .unwrap_err();
+
+        assert!(matches!(err, ParserError::InvalidPatch { .. }), "unexpected error: {err}");
+    }
+}
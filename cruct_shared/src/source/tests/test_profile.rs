@@ -0,0 +1,160 @@
+use std::fs::write;
+
+use tempfile::tempdir;
+
+use crate::{ConfigBuilder, ConfigFileSource, ConfigSource, ConfigValue};
+
+const PROFILES: &str = r#"
+[default]
+host = "localhost"
+port = 8080
+
+[dev]
+port = 3000
+
+[prod]
+host = "example.com"
+"#;
+
+fn section(entries: &[(&str, &str)]) -> ConfigValue {
+    ConfigValue::Section(
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), ConfigValue::Value(v.to_string())))
+            .collect(),
+    )
+}
+
+#[test]
+fn profile_table_is_merged_over_default() {
+    let dir = tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("config.toml");
+    write(&path, PROFILES).unwrap();
+
+    let src = ConfigFileSource::new(
+        path.to_str()
+            .unwrap(),
+        None,
+    )
+    .with_profiles();
+
+    assert_eq!(
+        src.load_profile("prod")
+            .unwrap(),
+        section(&[("host", "example.com"), ("port", "8080")])
+    );
+    assert_eq!(
+        src.load_profile("default")
+            .unwrap(),
+        section(&[("host", "localhost"), ("port", "8080")])
+    );
+    assert_eq!(
+        src.load_profile("staging")
+            .unwrap(),
+        section(&[("host", "localhost"), ("port", "8080")])
+    );
+}
+
+#[test]
+fn overlay_file_is_merged_on_top() {
+    let dir = tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("config.toml");
+    write(&path, "host = 'localhost'\nport = 8080").unwrap();
+    write(
+        dir.path()
+            .join("config.prod.toml"),
+        "host = 'example.com'",
+    )
+    .unwrap();
+
+    let cfg = ConfigBuilder::new()
+        .add_source(ConfigFileSource::new(
+            path.to_str()
+                .unwrap(),
+            None,
+        ))
+        .with_profile("prod")
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg, section(&[("host", "example.com"), ("port", "8080")]));
+}
+
+#[test]
+fn default_profile_is_used_without_active_profile() {
+    let dir = tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("config.toml");
+    write(&path, PROFILES).unwrap();
+
+    let cfg = ConfigBuilder::new()
+        .add_source(
+            ConfigFileSource::new(
+                path.to_str()
+                    .unwrap(),
+                None,
+            )
+            .with_profiles(),
+        )
+        .load()
+        .unwrap();
+
+    assert_eq!(cfg, section(&[("host", "localhost"), ("port", "8080")]));
+}
+
+#[test]
+fn default_tables_of_other_files_are_kept() {
+    let dir = tempdir().unwrap();
+    let path = dir
+        .path()
+        .join("config.toml");
+    write(&path, "[default]\nport = 1\n\n[server]\nport = 2").unwrap();
+
+    let cfg = ConfigBuilder::new()
+        .add_source(ConfigFileSource::new(
+            path.to_str()
+                .unwrap(),
+            None,
+        ))
+        .with_profile("prod")
+        .load()
+        .unwrap();
+
+    assert_eq!(
+        cfg,
+        ConfigValue::Section(
+            [
+                ("default".to_string(), section(&[("port", "1")])),
+                ("server".to_string(), section(&[("port", "2")])),
+            ]
+            .into()
+        )
+    );
+}
+
+#[test]
+fn active_profile_resolution() {
+    let builder = ConfigBuilder::new();
+    assert_eq!(builder.active_profile(), None);
+
+    let builder = builder.with_profile_env("CRUCT_TEST_UNSET_PROFILE_VARIABLE");
+    assert_eq!(
+        builder
+            .active_profile()
+            .as_deref(),
+        Some("default")
+    );
+
+    let builder = builder.with_profile("prod");
+    assert_eq!(
+        builder
+            .active_profile()
+            .as_deref(),
+        Some("prod")
+    );
+}
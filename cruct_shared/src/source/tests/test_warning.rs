@@ -0,0 +1,18 @@
+use crate::source::warning::{edit_distance, suggest};
+
+#[test]
+fn edit_distance_counts_single_character_edits() {
+    assert_eq!(edit_distance("http_port", "http_port"), 0);
+    assert_eq!(edit_distance("htpp_port", "http_port"), 1);
+    assert_eq!(edit_distance("prot", "port"), 2);
+    assert_eq!(edit_distance("", "abc"), 3);
+}
+
+#[test]
+fn suggest_picks_the_closest_key() {
+    let known = ["http_port", "https_port", "host"];
+
+    assert_eq!(suggest("htpp_port", &known), Some("http_port"));
+    assert_eq!(suggest("hostt", &known), Some("host"));
+    assert_eq!(suggest("database", &known), None);
+}
@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter, Result as DisplayResult};
+
+/// A problem found while loading a configuration that does not prevent it
+/// from loading, such as a deprecated key being set.
+///
+/// Warnings are collected by the [`LoadContext`](crate::LoadContext) the
+/// configuration is loaded with, see `Loader::load_with_warnings()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A deprecated key was set, such as the old name of a renamed field.
+    DeprecatedKey {
+        /// The dotted path of the key.
+        key: String,
+
+        /// The deprecation message, such as `use http_port instead`.
+        message: String,
+    },
+
+    /// A key that no field reads was set, such as a misspelled key.
+    ///
+    /// Reported as an error instead by structs annotated with
+    /// `#[cruct(deny_unknown_fields)]`.
+    UnknownKey {
+        /// The dotted path of the key.
+        key: String,
+
+        /// The most similar key of the same section, if any is close enough.
+        suggestion: Option<String>,
+    },
+
+    /// Keys of the same field were set more than once, such as a field and
+    /// one of its aliases, so the value of `key` is ignored.
+    ConflictingKey {
+        /// The dotted path of the ignored key.
+        key: String,
+
+        /// The dotted path of the key that was read instead.
+        used: String,
+    },
+
+    /// A flag that the command-line interface does not define was given, so
+    /// it was skipped.
+    ///
+    /// Reported as an error instead when the command line is parsed strictly.
+    UnknownFlag {
+        /// The flag name, without the leading `--`.
+        flag: String,
+
+        /// The most similar flag or key, if any is close enough.
+        suggestion: Option<String>,
+    },
+}
+
+impl Display for ConfigWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> DisplayResult {
+        match self {
+            ConfigWarning::DeprecatedKey { key, message } => {
+                write!(f, "Key '{key}' is deprecated: {message}")
+            },
+            ConfigWarning::UnknownKey { key, suggestion: None } => {
+                write!(f, "Unknown key '{key}'")
+            },
+            ConfigWarning::UnknownKey { key, suggestion: Some(suggestion) } => {
+                write!(f, "Unknown key '{key}', did you mean `{suggestion}`?")
+            },
+            ConfigWarning::ConflictingKey { key, used } => {
+                write!(f, "Key '{key}' is ignored, as '{used}' is also set")
+            },
+            ConfigWarning::UnknownFlag { flag, suggestion: None } => {
+                write!(f, "Unknown flag '--{flag}' is ignored")
+            },
+            ConfigWarning::UnknownFlag { flag, suggestion: Some(suggestion) } => {
+                write!(f, "Unknown flag '--{flag}' is ignored, did you mean `--{suggestion}`?")
+            },
+        }
+    }
+}
+
+/// Returns the candidate closest to `key`, if it is within a third of the
+/// length of `key` in edit distance, so that typos such as `htpp_port` are
+/// matched with the intended key.
+pub(crate) fn suggest<'a>(key: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let threshold = (key
+        .chars()
+        .count()
+        / 3)
+    .max(1);
+
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Computes the Levenshtein distance between two strings, the number of
+/// single character insertions, deletions and substitutions turning one
+/// into the other.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b
+        .chars()
+        .collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a
+        .chars()
+        .enumerate()
+    {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b
+            .iter()
+            .enumerate()
+        {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution
+                .min(row[j] + 1)
+                .min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -0,0 +1,138 @@
+//! The checks behind the validation parameters of `#[field]`, such as
+//! `range(min = 1, max = 65535)` or `pattern = "^[a-z]+$"`.
+//!
+//! Each check returns a message describing why the value is invalid, which
+//! the generated code reports as a `ParserError::Validation` error along with
+//! the path of the field.
+//!
+//! The `pattern` check requires the `regex` feature.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
+#[cfg(test)]
+mod tests;
+
+/// Types whose length can be checked with `length(min = .., max = ..)`.
+pub trait Length {
+    /// Returns the length of the value, in characters for strings and in
+    /// items for collections.
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars()
+            .count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str()
+            .length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for HashMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Checks that a value lies within the given inclusive bounds.
+///
+/// * `value`: The value to check.
+/// * `min`: The lowest accepted value, if any.
+/// * `max`: The highest accepted value, if any.
+pub fn range<T>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String>
+where
+    T: PartialOrd + Display,
+{
+    match (min, max) {
+        (Some(min), Some(max)) if *value < min || *value > max => {
+            Err(format!("must be between {min} and {max}, found {value}"))
+        },
+        (Some(min), None) if *value < min => Err(format!("must be at least {min}, found {value}")),
+        (None, Some(max)) if *value > max => Err(format!("must be at most {max}, found {value}")),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that the length of a value lies within the given inclusive bounds.
+///
+/// * `value`: The value to check.
+/// * `min`: The lowest accepted length, if any.
+/// * `max`: The highest accepted length, if any.
+pub fn length<T>(value: &T, min: Option<usize>, max: Option<usize>) -> Result<(), String>
+where
+    T: Length + ?Sized,
+{
+    range(&value.length(), min, max).map_err(|message| format!("length {message}"))
+}
+
+/// Checks that a string matches a regular expression.
+///
+/// The expression is not anchored, use `^` and `$` to match the whole value.
+/// The generated code compiles it once per field.
+///
+/// * `value`: The value to check.
+/// * `pattern`: The regular expression.
+#[cfg(feature = "regex")]
+pub fn pattern(value: &str, pattern: &Regex) -> Result<(), String> {
+    match pattern.is_match(value) {
+        true => Ok(()),
+        false => Err(format!("must match the pattern `{pattern}`, found '{value}'")),
+    }
+}
+
+/// Checks that a regular expression is valid, returning the reason it is
+/// not otherwise.
+///
+/// Used by the `#[cruct]` macro to reject invalid patterns at compile time.
+#[cfg(feature = "regex")]
+pub fn check_pattern(pattern: &str) -> Result<(), String> {
+    Regex::new(pattern)
+        .map(drop)
+        .map_err(|e| e.to_string())
+}
+
+/// Checks that a value is one of the allowed ones.
+///
+/// * `value`: The value to check.
+/// * `allowed`: The accepted values.
+pub fn one_of<T, U>(value: &T, allowed: &[U]) -> Result<(), String>
+where
+    T: PartialEq<U> + Display + ?Sized,
+    U: Display,
+{
+    if allowed
+        .iter()
+        .any(|allowed| value == allowed)
+    {
+        return Ok(());
+    }
+
+    let allowed = allowed
+        .iter()
+        .map(|allowed| format!("`{allowed}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Err(format!("must be one of {allowed}, found '{value}'"))
+}
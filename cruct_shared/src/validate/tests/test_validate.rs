@@ -0,0 +1,50 @@
+#[cfg(feature = "regex")]
+use crate::validate::{Regex, check_pattern, pattern};
+use crate::validate::{length, one_of, range};
+
+#[test]
+fn range_checks_both_bounds() {
+    assert!(range(&8080_u16, Some(1), Some(65535)).is_ok());
+    assert_eq!(
+        range(&0_u16, Some(1), Some(65535)),
+        Err("must be between 1 and 65535, found 0".into())
+    );
+    assert_eq!(range(&0.5, Some(1.0), None), Err("must be at least 1, found 0.5".into()));
+    assert_eq!(range(&12_i32, None, Some(10)), Err("must be at most 10, found 12".into()));
+}
+
+#[test]
+fn length_counts_characters_and_items() {
+    assert!(length("héllo", Some(5), Some(5)).is_ok());
+    assert_eq!(
+        length(&String::new(), Some(1), None),
+        Err("length must be at least 1, found 0".into())
+    );
+    assert_eq!(
+        length(&vec![1, 2, 3], None, Some(2)),
+        Err("length must be at most 2, found 3".into())
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn pattern_matches_regular_expressions() {
+    let regex = Regex::new("^[a-z]+$").unwrap();
+
+    assert!(pattern("app", &regex).is_ok());
+    assert_eq!(
+        pattern("App1", &regex),
+        Err("must match the pattern `^[a-z]+$`, found 'App1'".into())
+    );
+    assert!(check_pattern("[a-z").is_err());
+}
+
+#[test]
+fn one_of_lists_the_allowed_values() {
+    assert!(one_of(&String::from("info"), &["debug", "info"]).is_ok());
+    assert!(one_of(&2_u8, &[1, 2]).is_ok());
+    assert_eq!(
+        one_of(&String::from("trace"), &["debug", "info"]),
+        Err("must be one of `debug`, `info`, found 'trace'".into())
+    );
+}